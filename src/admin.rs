@@ -1,7 +1,18 @@
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
-use crate::auction::AuctionType;
+use crate::{
+    auction::{
+        Auction, AuctionType, BlacklistEntry, CreatorSource, Feature, FeeTier, GovernanceAction,
+        Role,
+    },
+    MAX_BULK_ITEMS, PERCENTAGE_TOTAL,
+};
+
+// Maximum number of epochs the rolling trade volume can span
+const MAX_VOLUME_WINDOW_EPOCHS: u64 = 30;
+// Maximum number of fee tiers that can be configured
+const MAX_FEE_TIERS: usize = 10;
 
 #[multiversx_sc::module]
 pub trait AdminModule:
@@ -11,10 +22,16 @@ pub trait AdminModule:
     + crate::events::EventsModule
     + crate::common::CommonModule
     + crate::wrapping::WrappingModule
+    + crate::pools::PoolsModule
+    + crate::fees::FeesModule
+    + crate::staking::StakingModule
+    + crate::claims::ClaimsModule
+    + crate::referrals::ReferralsModule
+    + crate::governance::GovernanceModule
 {
     #[endpoint(returnListing)]
     fn return_listing(&self, auction_ids: MultiValueEncoded<u64>) {
-        self.require_admin(None);
+        self.require_role(Role::ListingModerator, None);
         // SECURITY FIX: Get frozen auctions map to prevent interference during DEX swaps
         let map_frozen = self.freezed_auctions();
         for auction_id in auction_ids {
@@ -28,32 +45,110 @@ pub trait AdminModule:
             if map_auction.is_empty() {
                 continue;
             }
-            let mut auction = map_auction.get();
-            if auction.auction_type == AuctionType::SftOnePerPayment
-                || auction.auction_type == AuctionType::Nft
-            {
-                self.withdraw_auction_common(auction_id, &auction);
-            } else if auction.current_winner.is_zero() {
-                self.end_auction_common(auction_id, &auction);
-            } else if auction.current_winner != ManagedAddress::zero() {
-                self.transfer_or_save_payment(
-                    &auction.current_winner,
-                    &auction.payment_token_type,
-                    auction.payment_token_nonce,
-                    &auction.current_bid,
-                );
-                self.listings_bids(&auction.current_winner)
-                    .remove(&auction_id);
-
-                auction.current_winner = ManagedAddress::zero();
-                self.end_auction_common(auction_id, &auction);
+            self.return_listing_common(auction_id, map_auction.get());
+        }
+    }
+
+    // Delists a blacklisted wallet and refunds its open bids, in pages of MAX_BULK_ITEMS.
+    // Returns the auction id to resume from, 0 once the wallet was fully purged
+    #[endpoint(purgeBlacklistedWallet)]
+    fn purge_blacklisted_wallet(&self, wallet: ManagedAddress, from: OptionalValue<u64>) -> u64 {
+        self.require_role(Role::ListingModerator, None);
+        require!(
+            self.is_blacklisted(&wallet),
+            "The address is not blacklisted!"
+        );
+        let map_frozen = self.freezed_auctions();
+        let map_listings = self.listings_by_wallet(&wallet);
+        let map_bids = self.listings_bids(&wallet);
+        let from = from.into_option().unwrap_or_default();
+        // A cursor pointing into the bids means every listing was already handled
+        let resume_bids = from != 0 && map_bids.contains(&from);
+        // Enough ids to fill a page even if every frozen auction sits in it
+        let page_size = MAX_BULK_ITEMS + map_frozen.len() + 1;
+        let mut processed = 0;
+
+        if !resume_bids {
+            let iter = if from == 0 || !map_listings.contains(&from) {
+                map_listings.iter()
+            } else {
+                map_listings.iter_from(&from)
+            };
+            let auction_ids: ManagedVec<u64> = iter.take(page_size).collect();
+            for auction_id in auction_ids.iter() {
+                // Auctions in the middle of a swap are picked up by a later purge
+                if map_frozen.contains(&auction_id) {
+                    continue;
+                }
+                if processed == MAX_BULK_ITEMS {
+                    return auction_id;
+                }
+                self.return_listing_common(auction_id, self.try_get_auction(auction_id));
+                processed += 1;
             }
         }
+
+        let iter = if resume_bids {
+            map_bids.iter_from(&from)
+        } else {
+            map_bids.iter()
+        };
+        let bid_ids: ManagedVec<u64> = iter.take(page_size).collect();
+        for auction_id in bid_ids.iter() {
+            if map_frozen.contains(&auction_id) {
+                continue;
+            }
+            if processed == MAX_BULK_ITEMS {
+                return auction_id;
+            }
+            let mut auction = self.try_get_auction(auction_id);
+            self.transfer_or_save_payment(
+                &wallet,
+                &auction.payment_token_type,
+                auction.payment_token_nonce,
+                &auction.current_bid,
+            );
+            self.emit_bid_refund_event(
+                auction_id,
+                &wallet,
+                &auction.payment_token_type,
+                auction.payment_token_nonce,
+                &auction.current_bid,
+            );
+            self.listings_bids(&wallet).remove(&auction_id);
+            auction.current_winner = ManagedAddress::zero();
+            auction.current_bid = BigUint::zero();
+            self.auction_by_id(auction_id).set(&auction);
+            processed += 1;
+        }
+        0
+    }
+
+    fn return_listing_common(&self, auction_id: u64, mut auction: Auction<Self::Api>) {
+        if auction.auction_type == AuctionType::SftOnePerPayment
+            || auction.auction_type == AuctionType::Nft
+        {
+            self.withdraw_auction_common(auction_id, &auction);
+        } else if auction.current_winner.is_zero() {
+            self.end_auction_common(auction_id, &auction);
+        } else if auction.current_winner != ManagedAddress::zero() {
+            self.transfer_or_save_payment(
+                &auction.current_winner,
+                &auction.payment_token_type,
+                auction.payment_token_nonce,
+                &auction.current_bid,
+            );
+            self.listings_bids(&auction.current_winner)
+                .remove(&auction_id);
+
+            auction.current_winner = ManagedAddress::zero();
+            self.end_auction_common(auction_id, &auction);
+        }
     }
 
     #[endpoint(withdrawGlobalOffers)]
     fn withdraw_global_offers(&self, offer_ids: MultiValueEncoded<u64>) {
-        self.require_admin(None);
+        self.require_role(Role::ListingModerator, None);
         for offer_id in offer_ids {
             let map_offer = self.global_offer(offer_id);
             if map_offer.is_empty() {
@@ -66,7 +161,7 @@ pub trait AdminModule:
 
     #[endpoint(withdrawCustomOffers)]
     fn delete_custom_offers(&self, offer_ids: MultiValueEncoded<u64>) {
-        self.require_admin(None);
+        self.require_role(Role::ListingModerator, None);
         for offer_id in offer_ids {
             let map_offer = self.offer_by_id(offer_id);
             if map_offer.is_empty() {
@@ -78,6 +173,27 @@ pub trait AdminModule:
     }
 
     #[only_owner]
+    #[endpoint(grantRole)]
+    fn grant_role(&self, role: Role, address: ManagedAddress) {
+        require!(!address.is_zero(), "Invalid role address!");
+        if self.role_members(role).insert(address.clone()) {
+            self.emit_role_granted_event(role, &address);
+        }
+    }
+
+    #[only_owner]
+    #[endpoint(revokeRole)]
+    fn revoke_role(&self, role: Role, address: ManagedAddress) {
+        if self.role_members(role).swap_remove(&address) {
+            self.emit_role_revoked_event(role, &address);
+        }
+    }
+
+    #[view(hasRole)]
+    fn has_role(&self, role: Role, address: ManagedAddress) -> bool {
+        self.role_members(role).contains(&address)
+    }
+
     #[payable("*")]
     #[endpoint(addRewardBalance)]
     fn add_reward_balance(
@@ -85,6 +201,7 @@ pub trait AdminModule:
         #[payment_token] token: EgldOrEsdtTokenIdentifier,
         #[payment_amount] amount: BigUint,
     ) {
+        self.require_role(Role::RewardManager, None);
         require!(
             self.reward_ticker().get() == token,
             "This token is not used for rewards!"
@@ -92,9 +209,9 @@ pub trait AdminModule:
         self.reward_balance().update(|qt| *qt += &amount);
     }
 
-    #[only_owner]
     #[endpoint(removeRewardBalance)]
     fn remove_reward_balance(&self) {
+        self.require_role(Role::RewardManager, None);
         let token = self.reward_ticker().get();
         let ticker = &token.clone().into_esdt_option().unwrap();
         let balance =
@@ -117,51 +234,43 @@ pub trait AdminModule:
         map.set(token);
     }
 
-    #[only_owner]
     #[endpoint(setDefaultRewardAmount)]
     fn set_default_reward_amount(&self, amount: BigUint) {
+        self.require_role(Role::RewardManager, None);
         self.reward_amount().set(amount);
     }
 
     #[endpoint(setAcceptedTokens)]
     fn set_accepted_tokens(&self, token: EgldOrEsdtTokenIdentifier) {
-        self.require_admin(None);
-        self.accepted_tokens().insert(token);
+        self.require_role(Role::FeeManager, None);
+        self.queue_action(GovernanceAction::AddAcceptedToken(token));
     }
 
+    // Returns whether the token is accepted and a removal got queued
     #[endpoint(removeAcceptedTokens)]
     fn remove_accepted_tokens(&self, token: EgldOrEsdtTokenIdentifier) -> bool {
-        self.require_admin(None);
-        self.accepted_tokens().remove(&token)
+        self.require_role(Role::FeeManager, None);
+        if !self.accepted_tokens().contains(&token) {
+            return false;
+        }
+        self.queue_action(GovernanceAction::RemoveAcceptedToken(token));
+        true
     }
 
     #[endpoint(addWhitelist)]
     fn add_whitelisted_sc(&self, sc: ManagedAddress) {
-        self.require_admin(None);
+        self.require_role(Role::ListingModerator, None);
         require!(
             self.blockchain().is_smart_contract(&sc),
             "The address is not a smart contract!"
         );
-        let mut tokens = self.claimable_tokens(&sc);
-        for token in tokens.iter() {
-            let mut nonces = self.claimable_token_nonces(&sc, &token);
-            for nonce in nonces.iter() {
-                let amount_map = self.claimable_amount(&sc, &token, nonce);
-                let amount = amount_map.get();
-                if amount > BigUint::zero() {
-                    self.send().direct(&sc, &token, nonce, &amount_map.get());
-                    amount_map.clear();
-                }
-            }
-            nonces.clear();
-        }
-        tokens.clear();
+        self.release_all_claimables(&sc, &sc);
         self.whitelisted_contracts().insert(sc);
     }
 
     #[endpoint(removeWhitelist)]
     fn remove_wl_sc(&self, sc: ManagedAddress) {
-        self.require_admin(None);
+        self.require_role(Role::ListingModerator, None);
         require!(
             self.blockchain().is_smart_contract(&sc),
             "The address is not a smart contract!"
@@ -169,45 +278,298 @@ pub trait AdminModule:
         self.whitelisted_contracts().remove(&sc);
     }
 
+    // Pausing applies at once to contain incidents, resuming goes through the timelock
     #[endpoint(setStatus)]
     fn set_status(&self, status: bool) {
-        self.require_admin(None);
-        self.status().set(status);
+        self.require_role(Role::Pauser, None);
+        if status {
+            self.queue_action(GovernanceAction::SetStatus(status));
+        } else {
+            self.status().set(status);
+        }
+    }
+
+    #[endpoint(setFeaturePaused)]
+    fn set_feature_paused(&self, feature: Feature, paused: bool) {
+        self.require_role(Role::Pauser, None);
+        self.feature_paused(feature).set(paused);
+        self.emit_feature_pause_event(feature, paused);
+    }
+
+    #[endpoint(haltCollection)]
+    fn halt_collection(&self, collection: TokenIdentifier) {
+        self.require_role(Role::Pauser, None);
+        if self.halted_collections().insert(collection.clone()) {
+            self.emit_collection_halt_event(&collection, true);
+        }
+    }
+
+    #[endpoint(resumeCollection)]
+    fn resume_collection(&self, collection: TokenIdentifier) {
+        self.require_role(Role::Pauser, None);
+        if self.halted_collections().swap_remove(&collection) {
+            self.emit_collection_halt_event(&collection, false);
+        }
+    }
+
+    #[endpoint(setStrictBalanceMode)]
+    fn set_strict_balance_mode(&self, enabled: bool) {
+        self.require_role(Role::FeeManager, None);
+        self.strict_balance_mode().set(enabled);
+    }
+
+    #[only_owner]
+    #[endpoint(setLiquidStaking)]
+    fn set_liquid_staking(&self, sc: ManagedAddress, token: TokenIdentifier) {
+        require!(
+            self.blockchain().is_smart_contract(&sc),
+            "The address is not a smart contract!"
+        );
+        require!(
+            token.is_valid_esdt_identifier(),
+            "Invalid token identifier!"
+        );
+        require!(
+            self.total_yield_shares().get() == 0
+                && self.pending_unbond_shares().get() == 0
+                && self.yield_unbond_nonces().is_empty(),
+            "Cannot change liquid staking while shares are outstanding!"
+        );
+        self.yield_rate().clear();
+        self.liquid_staking_sc().set(sc);
+        self.liquid_staking_token().set(token);
+    }
+
+    #[endpoint(setFeeTiers)]
+    fn set_fee_tiers(&self, window_epochs: u64, tiers: MultiValueEncoded<FeeTier<Self::Api>>) {
+        self.require_role(Role::FeeManager, None);
+        let tiers = tiers.to_vec();
+        require!(tiers.len() <= MAX_FEE_TIERS, "Too many fee tiers!");
+        require!(
+            window_epochs <= MAX_VOLUME_WINDOW_EPOCHS,
+            "Volume window cannot exceed 30 epochs!"
+        );
+        require!(
+            tiers.is_empty() || window_epochs > 0,
+            "Volume window must be higher than 0!"
+        );
+        let mut last_min_volume = BigUint::zero();
+        for (index, tier) in tiers.iter().enumerate() {
+            require!(
+                index == 0 || tier.min_volume > last_min_volume,
+                "Fee tiers must be sorted by ascending volume!"
+            );
+            require!(
+                tier.discount <= PERCENTAGE_TOTAL,
+                "Fee tier discount cannot exceed 100%!"
+            );
+            last_min_volume = tier.min_volume.clone();
+        }
+        self.volume_window_epochs().set(window_epochs);
+        self.fee_tiers().set(&tiers);
+        self.emit_fee_tiers_event(window_epochs, &tiers);
+    }
+
+    #[only_owner]
+    #[endpoint(setXoxnoToken)]
+    fn set_xoxno_token(&self, token: TokenIdentifier) {
+        let map = self.xoxno_token();
+        require!(map.is_empty(), "The XOXNO token was already set!");
+        require!(
+            token.is_valid_esdt_identifier(),
+            "Invalid token identifier!"
+        );
+        map.set(token);
+    }
+
+    #[endpoint(setXoxnoHolderDiscount)]
+    fn set_xoxno_holder_discount(&self, threshold: BigUint, discount: BigUint) {
+        self.require_role(Role::FeeManager, None);
+        require!(discount <= PERCENTAGE_TOTAL, "Discount cannot exceed 100%!");
+        self.xoxno_holder_threshold().set(threshold);
+        self.xoxno_holder_discount().set(discount);
+    }
+
+    #[endpoint(setXoxnoLockPeriod)]
+    fn set_xoxno_lock_period(&self, period: u64) {
+        self.require_role(Role::FeeManager, None);
+        self.xoxno_lock_period().set(period);
+    }
+
+    // Rates older than `max_age` seconds are ignored, 0 disables the check
+    #[endpoint(setXoxnoRateMaxAge)]
+    fn set_xoxno_rate_max_age(&self, max_age: u64) {
+        self.require_role(Role::FeeManager, None);
+        self.xoxno_rate_max_age().set(max_age);
+    }
+
+    #[endpoint(setXoxnoFeePayment)]
+    fn set_xoxno_fee_payment(
+        &self,
+        discount: BigUint,
+        rates: MultiValueEncoded<MultiValue2<EgldOrEsdtTokenIdentifier, BigUint>>,
+    ) {
+        self.require_role(Role::FeeManager, None);
+        require!(discount <= PERCENTAGE_TOTAL, "Discount cannot exceed 100%!");
+        self.xoxno_fee_discount().set(discount);
+        let current_time = self.current_timestamp();
+        for rate in rates {
+            let (token, amount) = rate.into_tuple();
+            self.xoxno_fee_rate(&token).set(amount);
+            self.xoxno_fee_rate_updated(&token).set(current_time);
+        }
     }
 
     #[only_owner]
     #[endpoint(setCutPercentage)]
     fn set_percentage_cut(&self, new_cut_percentage: u64) {
-        self.try_set_bid_cut_percentage(new_cut_percentage)
+        self.queue_action(GovernanceAction::SetCutPercentage(new_cut_percentage));
+    }
+
+    #[endpoint(setCreatorSource)]
+    fn set_creator_source(&self, collection: TokenIdentifier, source: CreatorSource<Self::Api>) {
+        self.require_role(Role::FeeManager, None);
+        if let CreatorSource::Fixed(creator) = &source {
+            require!(!creator.is_zero(), "Invalid creator address!");
+        }
+        self.creator_source(&collection).set(&source);
+        self.emit_creator_source_event(&collection, OptionalValue::Some(source));
+    }
+
+    #[endpoint(removeCreatorSource)]
+    fn remove_creator_source(&self, collection: TokenIdentifier) {
+        self.require_role(Role::FeeManager, None);
+        self.creator_source(&collection).clear();
+        self.emit_creator_source_event(&collection, OptionalValue::None);
+    }
+
+    #[endpoint(setReferrer)]
+    fn set_referrer(&self, referrer: ManagedAddress, share: u64) {
+        self.require_role(Role::FeeManager, None);
+        require!(!referrer.is_zero(), "Invalid referrer address!");
+        require!(
+            share > 0 && share <= PERCENTAGE_TOTAL,
+            "Invalid percentage value, should be between 0 and 10,000"
+        );
+        let share = BigUint::from(share);
+        self.referrer_share(&referrer).set(&share);
+        self.referrers().insert(referrer.clone());
+        self.emit_referrer_event(&referrer, &share);
+    }
+
+    #[endpoint(removeReferrer)]
+    fn remove_referrer(&self, referrer: ManagedAddress) {
+        self.require_role(Role::FeeManager, None);
+        self.referrer_share(&referrer).clear();
+        self.referrers().swap_remove(&referrer);
+        self.emit_referrer_event(&referrer, &BigUint::zero());
+    }
+
+    #[only_owner]
+    #[endpoint(setTokenCutPercentage)]
+    fn set_token_cut_percentage(
+        &self,
+        payment_token: EgldOrEsdtTokenIdentifier,
+        new_cut_percentage: u64,
+    ) {
+        self.queue_action(GovernanceAction::SetTokenCutPercentage(
+            payment_token,
+            new_cut_percentage,
+        ));
+    }
+
+    #[only_owner]
+    #[endpoint(removeTokenCutPercentage)]
+    fn remove_token_cut_percentage(&self, payment_token: EgldOrEsdtTokenIdentifier) {
+        self.queue_action(GovernanceAction::RemoveTokenCutPercentage(payment_token));
     }
 
     #[endpoint(unFreezeAuctionId)]
     fn un_freeze_auction_id(&self, auction_id: u64) {
-        self.require_admin(None);
+        self.require_role(Role::Signer, None);
         self.freezed_auctions().swap_remove(&auction_id);
     }
 
     #[endpoint(unFreezeAllAuctionIds)]
     fn un_freeze_all_auction_id(&self) {
-        self.require_admin(None);
+        self.require_role(Role::Signer, None);
         self.freezed_auctions().clear();
     }
 
     #[endpoint(freezeAuctionId)]
     fn freeze_auction_id(&self, auction_id: u64) {
-        self.require_admin(None);
+        self.require_role(Role::Signer, None);
         self.freezed_auctions().insert(auction_id);
     }
 
+    #[endpoint(addDeniedCollections)]
+    fn add_denied_collections(&self, collections: MultiValueEncoded<TokenIdentifier>) {
+        self.require_role(Role::ListingModerator, None);
+        self.denied_collections().extend(collections);
+    }
+
+    #[endpoint(removeDeniedCollections)]
+    fn remove_denied_collections(&self, collections: MultiValueEncoded<TokenIdentifier>) {
+        self.require_role(Role::ListingModerator, None);
+        let mut map_denied = self.denied_collections();
+        for collection in collections {
+            map_denied.swap_remove(&collection);
+        }
+    }
+
+    #[endpoint(addAllowedCollections)]
+    fn add_allowed_collections(&self, collections: MultiValueEncoded<TokenIdentifier>) {
+        self.require_role(Role::ListingModerator, None);
+        self.allowed_collections().extend(collections);
+    }
+
+    #[endpoint(removeAllowedCollections)]
+    fn remove_allowed_collections(&self, collections: MultiValueEncoded<TokenIdentifier>) {
+        self.require_role(Role::ListingModerator, None);
+        let mut map_allowed = self.allowed_collections();
+        for collection in collections {
+            map_allowed.swap_remove(&collection);
+        }
+    }
+
+    #[endpoint(setCuratedMode)]
+    fn set_curated_mode(&self, enabled: bool) {
+        self.require_role(Role::ListingModerator, None);
+        self.curated_mode().set(enabled);
+    }
+
+    #[allow_multiple_var_args]
     #[endpoint(addBlackListWallet)]
-    fn add_blacklist(&self, wallet: ManagedAddress) -> bool {
-        self.require_admin(None);
+    fn add_blacklist(
+        &self,
+        wallet: ManagedAddress,
+        reason: OptionalValue<u32>,
+        expires_at: OptionalValue<u64>,
+    ) -> bool {
+        self.require_role(Role::ListingModerator, None);
+        let current_time = self
+            .blockchain()
+            .get_block_timestamp_seconds()
+            .as_u64_seconds();
+        let expires_at = expires_at.into_option().unwrap_or_default();
+        require!(
+            expires_at == 0 || expires_at > current_time,
+            "Expiry can't be in the past!"
+        );
+        let entry = BlacklistEntry {
+            reason: reason.into_option().unwrap_or_default(),
+            timestamp: current_time,
+            expires_at,
+        };
+        self.blacklist_entry(&wallet).set(&entry);
+        self.emit_blacklist_event(&wallet, &entry);
         self.blacklist_wallets().insert(wallet)
     }
 
     #[endpoint(removeBlackListWallet)]
     fn remove_blacklist(&self, wallet: ManagedAddress) -> bool {
-        self.require_admin(None);
+        self.require_role(Role::ListingModerator, None);
+        self.blacklist_entry(&wallet).clear();
         self.blacklist_wallets().remove(&wallet)
     }
 }
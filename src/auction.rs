@@ -4,7 +4,7 @@ multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
 #[type_abi]
-#[derive(ManagedVecItem, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+#[derive(ManagedVecItem, TopEncode, NestedEncode, NestedDecode, Clone)]
 pub struct Auction<M: ManagedTypeApi> {
     pub auctioned_token_type: TokenIdentifier<M>,
     pub auctioned_token_nonce: u64,
@@ -22,6 +22,71 @@ pub struct Auction<M: ManagedTypeApi> {
     pub current_winner: ManagedAddress<M>,
     pub marketplace_cut_percentage: BigUint<M>,
     pub creator_royalties_percentage: BigUint<M>,
+    pub reserved_buyer: Option<ManagedAddress<M>>,
+    pub merkle_root: Option<ManagedByteArray<M, 32>>,
+    // Every escrowed item of a bundle listing, empty for single token listings
+    pub bundle_items: ManagedVec<M, EsdtTokenPayment<M>>,
+}
+
+impl<M: ManagedTypeApi> TopDecode for Auction<M> {
+    fn top_decode<I>(input: I) -> Result<Self, DecodeError>
+    where
+        I: multiversx_sc::codec::TopDecodeInput,
+    {
+        let mut input = input.into_nested_buffer();
+        let auctioned_token_type = TokenIdentifier::dep_decode(&mut input)?;
+        let auctioned_token_nonce = u64::dep_decode(&mut input)?;
+        let nr_auctioned_tokens = BigUint::dep_decode(&mut input)?;
+        let auction_type = AuctionType::dep_decode(&mut input)?;
+        let payment_token_type = EgldOrEsdtTokenIdentifier::dep_decode(&mut input)?;
+        let payment_token_nonce = u64::dep_decode(&mut input)?;
+        let min_bid = BigUint::dep_decode(&mut input)?;
+        let max_bid = Option::<BigUint<M>>::dep_decode(&mut input)?;
+        let start_time = u64::dep_decode(&mut input)?;
+        let deadline = u64::dep_decode(&mut input)?;
+        let original_owner = ManagedAddress::dep_decode(&mut input)?;
+        let current_bid = BigUint::dep_decode(&mut input)?;
+        let current_winner = ManagedAddress::dep_decode(&mut input)?;
+        let marketplace_cut_percentage = BigUint::dep_decode(&mut input)?;
+        let creator_royalties_percentage = BigUint::dep_decode(&mut input)?;
+
+        let reserved_buyer = if input.is_depleted() {
+            None
+        } else {
+            Option::<ManagedAddress<M>>::dep_decode(&mut input)?
+        };
+        let merkle_root = if input.is_depleted() {
+            None
+        } else {
+            Option::<ManagedByteArray<M, 32>>::dep_decode(&mut input)?
+        };
+        let bundle_items = if input.is_depleted() {
+            ManagedVec::new()
+        } else {
+            ManagedVec::<M, EsdtTokenPayment<M>>::dep_decode(&mut input)?
+        };
+
+        Result::Ok(Auction {
+            auctioned_token_type,
+            auctioned_token_nonce,
+            nr_auctioned_tokens,
+            auction_type,
+            payment_token_type,
+            payment_token_nonce,
+            min_bid,
+            max_bid,
+            start_time,
+            deadline,
+            original_owner,
+            current_bid,
+            current_winner,
+            marketplace_cut_percentage,
+            creator_royalties_percentage,
+            reserved_buyer,
+            merkle_root,
+            bundle_items,
+        })
+    }
 }
 
 #[type_abi]
@@ -39,6 +104,8 @@ pub struct Offer<M: ManagedTypeApi> {
     pub offer_owner: ManagedAddress<M>,
     pub marketplace_cut_percentage: BigUint<M>,
     pub new_version: bool,
+    // Whether the price is part of the owner's committed balance
+    pub committed: bool,
 }
 
 impl<M: ManagedTypeApi> TopDecode for Offer<M> {
@@ -64,6 +131,11 @@ impl<M: ManagedTypeApi> TopDecode for Offer<M> {
         } else {
             bool::dep_decode(&mut input)?
         };
+        let committed = if input.is_depleted() {
+            false
+        } else {
+            bool::dep_decode(&mut input)?
+        };
 
         Result::Ok(Offer {
             token_type,
@@ -78,6 +150,7 @@ impl<M: ManagedTypeApi> TopDecode for Offer<M> {
             offer_owner,
             marketplace_cut_percentage,
             new_version,
+            committed,
         })
     }
 }
@@ -125,6 +198,9 @@ pub struct FeesDistribution<M: ManagedTypeApi> {
     pub extra_address: ManagedAddress<M>,
     pub reverse_royalties: bool,
     pub reverse_cut_fees: bool,
+    pub referral: BigUint<M>,
+    pub referrer: ManagedAddress<M>,
+    pub royalty_splits: ManagedVec<M, RoyaltySplit<M>>,
 }
 
 #[type_abi]
@@ -140,6 +216,8 @@ pub struct GlobalOffer<M: ManagedTypeApi> {
     pub owner: ManagedAddress<M>,
     pub attributes: Option<ManagedBuffer<M>>,
     pub new_version: bool,
+    // Whether price * quantity is part of the owner's committed balance
+    pub committed: bool,
 }
 
 impl<M: ManagedTypeApi> TopDecode for GlobalOffer<M> {
@@ -168,6 +246,11 @@ impl<M: ManagedTypeApi> TopDecode for GlobalOffer<M> {
         } else {
             bool::dep_decode(&mut input)?
         };
+        let committed = if input.is_depleted() {
+            false
+        } else {
+            bool::dep_decode(&mut input)?
+        };
 
         Result::Ok(GlobalOffer {
             offer_id,
@@ -180,12 +263,25 @@ impl<M: ManagedTypeApi> TopDecode for GlobalOffer<M> {
             owner,
             attributes,
             new_version,
+            committed,
         })
     }
 }
 
 #[type_abi]
-#[derive(ManagedVecItem, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct BundleListing<M: ManagedTypeApi> {
+    pub min_bid: BigUint<M>,
+    pub max_bid: BigUint<M>,
+    pub deadline: u64,
+    pub accepted_payment_token: EgldOrEsdtTokenIdentifier<M>,
+    pub bid: bool,
+    pub opt_start_time: u64,
+    pub reserved_buyer: Option<ManagedAddress<M>>,
+}
+
+#[type_abi]
+#[derive(ManagedVecItem, TopEncode, NestedEncode, NestedDecode, Clone)]
 pub struct BulkListing<M: ManagedTypeApi> {
     pub min_bid: BigUint<M>,
     pub max_bid: BigUint<M>,
@@ -198,6 +294,55 @@ pub struct BulkListing<M: ManagedTypeApi> {
     pub nonce: u64,
     pub nft_amount: BigUint<M>,
     pub royalties: BigUint<M>,
+    pub reserved_buyer: Option<ManagedAddress<M>>,
+    pub merkle_root: Option<ManagedByteArray<M, 32>>,
+}
+
+impl<M: ManagedTypeApi> TopDecode for BulkListing<M> {
+    fn top_decode<I>(input: I) -> Result<Self, DecodeError>
+    where
+        I: multiversx_sc::codec::TopDecodeInput,
+    {
+        let mut input = input.into_nested_buffer();
+        let min_bid = BigUint::dep_decode(&mut input)?;
+        let max_bid = BigUint::dep_decode(&mut input)?;
+        let deadline = u64::dep_decode(&mut input)?;
+        let accepted_payment_token = EgldOrEsdtTokenIdentifier::dep_decode(&mut input)?;
+        let bid = bool::dep_decode(&mut input)?;
+        let opt_sft_max_one_per_payment = bool::dep_decode(&mut input)?;
+        let opt_start_time = u64::dep_decode(&mut input)?;
+        let collection = EgldOrEsdtTokenIdentifier::dep_decode(&mut input)?;
+        let nonce = u64::dep_decode(&mut input)?;
+        let nft_amount = BigUint::dep_decode(&mut input)?;
+        let royalties = BigUint::dep_decode(&mut input)?;
+
+        let reserved_buyer = if input.is_depleted() {
+            None
+        } else {
+            Option::<ManagedAddress<M>>::dep_decode(&mut input)?
+        };
+        let merkle_root = if input.is_depleted() {
+            None
+        } else {
+            Option::<ManagedByteArray<M, 32>>::dep_decode(&mut input)?
+        };
+
+        Result::Ok(BulkListing {
+            min_bid,
+            max_bid,
+            deadline,
+            accepted_payment_token,
+            bid,
+            opt_sft_max_one_per_payment,
+            opt_start_time,
+            collection,
+            nonce,
+            nft_amount,
+            royalties,
+            reserved_buyer,
+            merkle_root,
+        })
+    }
 }
 
 #[type_abi]
@@ -210,7 +355,7 @@ pub struct BulkUpdateListing<M: ManagedTypeApi> {
 }
 
 #[type_abi]
-#[derive(ManagedVecItem, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+#[derive(ManagedVecItem, TopEncode, NestedEncode, NestedDecode, Clone)]
 pub struct CollectionFeeConfig<M: ManagedTypeApi> {
     pub reverse_cut_fees: bool,
     pub reverse_royalties: bool,
@@ -219,6 +364,47 @@ pub struct CollectionFeeConfig<M: ManagedTypeApi> {
     pub max_royalties: BigUint<M>,
     pub extra_fees: CollectionExtraFeesConfig<M>,
     pub admin: ManagedAddress<M>,
+    pub royalty_splits: ManagedVec<M, RoyaltySplit<M>>,
+}
+
+impl<M: ManagedTypeApi> TopDecode for CollectionFeeConfig<M> {
+    fn top_decode<I>(input: I) -> Result<Self, DecodeError>
+    where
+        I: multiversx_sc::codec::TopDecodeInput,
+    {
+        let mut input = input.into_nested_buffer();
+        let reverse_cut_fees = bool::dep_decode(&mut input)?;
+        let reverse_royalties = bool::dep_decode(&mut input)?;
+        let custom_royalties = bool::dep_decode(&mut input)?;
+        let min_royalties = BigUint::dep_decode(&mut input)?;
+        let max_royalties = BigUint::dep_decode(&mut input)?;
+        let extra_fees = CollectionExtraFeesConfig::dep_decode(&mut input)?;
+        let admin = ManagedAddress::dep_decode(&mut input)?;
+
+        let royalty_splits = if input.is_depleted() {
+            ManagedVec::new()
+        } else {
+            ManagedVec::dep_decode(&mut input)?
+        };
+
+        Result::Ok(CollectionFeeConfig {
+            reverse_cut_fees,
+            reverse_royalties,
+            custom_royalties,
+            min_royalties,
+            max_royalties,
+            extra_fees,
+            admin,
+            royalty_splits,
+        })
+    }
+}
+
+#[type_abi]
+#[derive(ManagedVecItem, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct RoyaltySplit<M: ManagedTypeApi> {
+    pub address: ManagedAddress<M>,
+    pub share: BigUint<M>,
 }
 
 #[type_abi]
@@ -228,8 +414,216 @@ pub struct CollectionExtraFeesConfig<M: ManagedTypeApi> {
     pub address: ManagedAddress<M>,
 }
 
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq)]
+pub enum CreatorSource<M: ManagedTypeApi> {
+    // The creator address is stored raw in the token attributes, starting at `offset`
+    Attributes { offset: usize },
+    Fixed(ManagedAddress<M>),
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct BlacklistEntry {
+    pub reason: u32,
+    pub timestamp: u64,
+    // 0 means the entry never expires
+    pub expires_at: u64,
+}
+
+#[type_abi]
+#[derive(ManagedVecItem, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct FeeTier<M: ManagedTypeApi> {
+    pub min_volume: BigUint<M>,
+    pub discount: BigUint<M>,
+}
+
+// Offer and global offer ids are separate sequences, so the cursor names the set it points into
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq, Debug)]
+pub enum OffersCursor {
+    Done,
+    Offers(u64),
+    GlobalOffers(u64),
+}
+
+#[type_abi]
+#[derive(ManagedVecItem, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct VolumeBucket<M: ManagedTypeApi> {
+    pub epoch: u64,
+    pub volume: BigUint<M>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct UserFeeTier<M: ManagedTypeApi> {
+    pub tier: usize,
+    pub volume: BigUint<M>,
+    pub discount: BigUint<M>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq)]
+pub enum Feature {
+    Listing,
+    Buying,
+    Bidding,
+    Offers,
+    GlobalOffers,
+    Deposits,
+    Swaps,
+    Rentals,
+    Raffles,
+}
+
+pub const ALL_FEATURES: [Feature; 9] = [
+    Feature::Listing,
+    Feature::Buying,
+    Feature::Bidding,
+    Feature::Offers,
+    Feature::GlobalOffers,
+    Feature::Deposits,
+    Feature::Swaps,
+    Feature::Rentals,
+    Feature::Raffles,
+];
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq)]
+pub enum Role {
+    Pauser,
+    ListingModerator,
+    FeeManager,
+    RewardManager,
+    Signer,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub enum GovernanceAction<M: ManagedTypeApi> {
+    SetCutPercentage(u64),
+    SetTokenCutPercentage(EgldOrEsdtTokenIdentifier<M>, u64),
+    RemoveTokenCutPercentage(EgldOrEsdtTokenIdentifier<M>),
+    AddAcceptedToken(EgldOrEsdtTokenIdentifier<M>),
+    RemoveAcceptedToken(EgldOrEsdtTokenIdentifier<M>),
+    SetAggregator(ManagedAddress<M>),
+    SetAccumulator(ManagedAddress<M>),
+    SetWrapping(ManagedAddress<M>, TokenIdentifier<M>),
+    SetGovernanceDelay(u64),
+    SetExtraFees(TokenIdentifier<M>, BigUint<M>, ManagedAddress<M>),
+    SetStatus(bool),
+    SetClaimReceiver(ManagedAddress<M>, Option<ManagedAddress<M>>),
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct QueuedAction<M: ManagedTypeApi> {
+    pub action: GovernanceAction<M>,
+    pub executable_at: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct SignedOrder<M: ManagedTypeApi> {
+    pub seller: ManagedAddress<M>,
+    pub collection: TokenIdentifier<M>,
+    pub token_nonce: u64,
+    pub quantity: BigUint<M>,
+    pub payment_token: EgldOrEsdtTokenIdentifier<M>,
+    pub payment_nonce: u64,
+    pub price: BigUint<M>,
+    pub deadline: u64,
+    pub order_nonce: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct SignedOffer<M: ManagedTypeApi> {
+    pub buyer: ManagedAddress<M>,
+    pub collection: TokenIdentifier<M>,
+    // 0 makes the offer valid for any token of the collection
+    pub token_nonce: u64,
+    pub quantity: BigUint<M>,
+    pub payment_token: EgldOrEsdtTokenIdentifier<M>,
+    pub payment_nonce: u64,
+    pub price: BigUint<M>,
+    pub deadline: u64,
+    pub salt: u64,
+    pub counter: u64,
+}
+
 #[type_abi]
 #[derive(ManagedVecItem, TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
-pub struct AttributesIns<M: ManagedTypeApi> {
+pub struct SwapRequest<M: ManagedTypeApi> {
+    pub collection: TokenIdentifier<M>,
+    // 0 accepts any token of the collection
+    pub nonce: u64,
+    pub amount: BigUint<M>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct NftSwap<M: ManagedTypeApi> {
+    pub maker: ManagedAddress<M>,
+    pub offered: ManagedVec<M, EsdtTokenPayment<M>>,
+    pub sweetener: Option<EgldOrEsdtTokenPayment<M>>,
+    pub requested: ManagedVec<M, SwapRequest<M>>,
+    // The maker's swap royalties for the requested NFTs, set aside from the EGLD deposit
+    pub royalty_reserve: BigUint<M>,
+    pub deadline: u64,
+    pub timestamp: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct Rental<M: ManagedTypeApi> {
+    pub owner: ManagedAddress<M>,
+    pub collection: TokenIdentifier<M>,
+    pub nonce: u64,
+    pub amount: BigUint<M>,
+    pub payment_token: EgldOrEsdtTokenIdentifier<M>,
+    pub daily_price: BigUint<M>,
+    pub max_days: u64,
+    pub collateral: BigUint<M>,
+    pub creator: ManagedAddress<M>,
+    pub creator_royalties_percentage: BigUint<M>,
+    // Zero while the NFT is available in escrow
+    pub renter: ManagedAddress<M>,
+    pub rent_paid: BigUint<M>,
+    pub expires_at: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, PartialEq, Clone, Copy)]
+pub enum RaffleStatus {
+    Active,
+    Drawn,
+    Failed,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct Raffle<M: ManagedTypeApi> {
+    pub owner: ManagedAddress<M>,
+    pub collection: TokenIdentifier<M>,
+    pub nonce: u64,
+    pub amount: BigUint<M>,
+    pub payment_token: EgldOrEsdtTokenIdentifier<M>,
+    pub ticket_price: BigUint<M>,
+    pub max_tickets: u64,
+    pub min_tickets: u64,
+    pub deadline: u64,
     pub creator: ManagedAddress<M>,
+    pub creator_royalties_percentage: BigUint<M>,
+    pub tickets_sold: u64,
+    pub status: RaffleStatus,
+    pub winner: ManagedAddress<M>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone)]
+pub struct TicketBatch<M: ManagedTypeApi> {
+    pub buyer: ManagedAddress<M>,
+    // Exclusive upper bound of the ticket numbers bought in this batch
+    pub end: u64,
 }
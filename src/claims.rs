@@ -0,0 +1,128 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+#[multiversx_sc::module]
+pub trait ClaimsModule: crate::storage::StorageModule + crate::events::EventsModule {
+    #[endpoint(claim)]
+    fn claim(&self, owner: OptionalValue<ManagedAddress>) {
+        let caller = self.blockchain().get_caller();
+        let owner = owner.into_option().unwrap_or_else(|| caller.clone());
+        self.require_claim_allowed(&owner, &caller);
+        self.release_all_claimables(&owner, &caller);
+    }
+
+    #[endpoint(claimToken)]
+    fn claim_token(
+        &self,
+        token: EgldOrEsdtTokenIdentifier,
+        nonce: u64,
+        owner: OptionalValue<ManagedAddress>,
+    ) {
+        let caller = self.blockchain().get_caller();
+        let owner = owner.into_option().unwrap_or_else(|| caller.clone());
+        self.require_claim_allowed(&owner, &caller);
+        require!(
+            self.claimable_token_nonces(&owner, &token).contains(&nonce),
+            "Nothing to claim for this token!"
+        );
+        self.release_claimable(&owner, &caller, &token, nonce);
+
+        if self.claimable_token_nonces(&owner, &token).is_empty() {
+            self.claimable_tokens(&owner).remove(&token);
+        }
+    }
+
+    // Lets a contract that cannot receive payments nominate an address (e.g. its owner) to pull them.
+    // Contracts without an endpoint calling this get their receiver through the governance timelock
+    #[endpoint(setClaimReceiver)]
+    fn set_claim_receiver(&self, receiver: OptionalValue<ManagedAddress>) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            self.blockchain().is_smart_contract(&caller),
+            "Only smart contracts can set a claim receiver!"
+        );
+        self.apply_claim_receiver(&caller, receiver.into_option());
+    }
+
+    #[view(getClaimables)]
+    fn get_claimables(
+        &self,
+        owner: ManagedAddress,
+    ) -> MultiValueEncoded<MultiValue3<EgldOrEsdtTokenIdentifier, u64, BigUint>> {
+        let mut results = MultiValueEncoded::new();
+        for token in self.claimable_tokens(&owner).iter() {
+            for nonce in self.claimable_token_nonces(&owner, &token).iter() {
+                let amount = self.claimable_amount(&owner, &token, nonce).get();
+                if amount > 0 {
+                    results.push((token.clone(), nonce, amount).into());
+                }
+            }
+        }
+        results
+    }
+
+    fn apply_claim_receiver(&self, contract: &ManagedAddress, receiver: Option<ManagedAddress>) {
+        match receiver {
+            Some(receiver) => {
+                self.emit_claim_receiver_event(contract, &receiver);
+                self.claim_receiver(contract).set(receiver);
+            }
+            None => {
+                self.emit_claim_receiver_event(contract, &ManagedAddress::zero());
+                self.claim_receiver(contract).clear();
+            }
+        }
+    }
+
+    fn require_claim_allowed(&self, owner: &ManagedAddress, caller: &ManagedAddress) {
+        let map_receiver = self.claim_receiver(owner);
+        require!(
+            owner == caller || (!map_receiver.is_empty() && map_receiver.get() == *caller),
+            "You are not allowed to claim for this address!"
+        );
+    }
+
+    fn release_all_claimables(&self, owner: &ManagedAddress, to: &ManagedAddress) {
+        let mut tokens = self.claimable_tokens(owner);
+        for token in tokens.iter() {
+            let mut nonces = self.claimable_token_nonces(owner, &token);
+            for nonce in nonces.iter() {
+                let amount_map = self.claimable_amount(owner, &token, nonce);
+                let amount = amount_map.take();
+                if amount > 0 {
+                    self.send().direct(to, &token, nonce, &amount);
+                    self.emit_claim_event(
+                        owner,
+                        to,
+                        &EgldOrEsdtTokenPayment::new(token.clone(), nonce, amount),
+                    );
+                }
+            }
+            nonces.clear();
+        }
+        tokens.clear();
+    }
+
+    fn release_claimable(
+        &self,
+        owner: &ManagedAddress,
+        to: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+        nonce: u64,
+    ) {
+        let amount = self.claimable_amount(owner, token, nonce).take();
+        self.claimable_token_nonces(owner, token).remove(&nonce);
+        if amount > 0 {
+            self.send().direct(to, token, nonce, &amount);
+            self.emit_claim_event(
+                owner,
+                to,
+                &EgldOrEsdtTokenPayment::new(token.clone(), nonce, amount),
+            );
+        }
+    }
+
+    #[view(getClaimReceiver)]
+    #[storage_mapper("claimReceiver")]
+    fn claim_receiver(&self, owner: &ManagedAddress) -> SingleValueMapper<ManagedAddress>;
+}
@@ -3,8 +3,8 @@ multiversx_sc::derive_imports!();
 
 use crate::{
     accumulator,
-    auction::{Auction, AuctionType, FeesDistribution, GlobalOffer, Offer},
-    MIN_TRADE_REWARD, NFT_AMOUNT,
+    auction::{Auction, AuctionType, Feature, FeesDistribution, GlobalOffer, Offer, RoyaltySplit},
+    MIN_TRADE_REWARD, NFT_AMOUNT, PERCENTAGE_TOTAL,
 };
 
 #[multiversx_sc::module]
@@ -14,6 +14,10 @@ pub trait CommonModule:
     + crate::views::ViewsModule
     + crate::events::EventsModule
     + crate::wrapping::WrappingModule
+    + crate::pools::PoolsModule
+    + crate::fees::FeesModule
+    + crate::staking::StakingModule
+    + crate::referrals::ReferralsModule
 {
     fn withdraw_auction_common(&self, auction_id: u64, auction: &Auction<Self::Api>) {
         require!(
@@ -33,7 +37,12 @@ pub trait CommonModule:
         self.update_or_remove_items_quantity(auction, &auction.nr_auctioned_tokens);
         self.remove_auction_common(auction_id, auction);
         self.emit_end_auction_event(auction_id, auction);
-        self.distribute_tokens(auction, Option::Some(&auction.nr_auctioned_tokens), false);
+        self.distribute_tokens(
+            auction,
+            Option::Some(&auction.nr_auctioned_tokens),
+            false,
+            None,
+        );
     }
 
     fn common_bid_checks(
@@ -53,6 +62,12 @@ pub trait CommonModule:
             .blockchain()
             .get_block_timestamp_seconds()
             .as_u64_seconds();
+        self.require_collection_active(nft_type);
+        // A bundle is only tradable while every collection it holds is
+        for item in auction.bundle_items.iter() {
+            self.require_collection_active(&item.token_identifier);
+            self.require_collection_allowed(&item.token_identifier);
+        }
         require!(
             !self.freezed_auctions().contains(&auction_id),
             "Auction is frozen!"
@@ -65,6 +80,10 @@ pub trait CommonModule:
             auction.original_owner != caller,
             "Can't bid on your own token"
         );
+        require!(
+            !self.is_blacklisted(&auction.original_owner),
+            "The seller address was blacklisted!"
+        );
         require!(
             current_time >= auction.start_time,
             "Auction hasn't started yet"
@@ -127,8 +146,10 @@ pub trait CommonModule:
         buy_for: OptionalValue<ManagedAddress>,
         message: OptionalValue<ManagedBuffer>,
         swaps: OptionalValue<ManagedArgBuffer<Self::Api>>,
+        referrer: Option<ManagedAddress>,
+        proof: Option<ManagedVec<ManagedByteArray<32>>>,
     ) {
-        self.require_enabled();
+        self.require_feature_enabled(Feature::Buying);
         let payments = self.call_value().egld_or_single_esdt();
         let (payment_token, payment_token_nonce, payment_amount) = payments.clone().into_tuple();
         let mut auction = self.try_get_auction(auction_id);
@@ -145,6 +166,18 @@ pub trait CommonModule:
         };
 
         let total_value = &buy_amount * &auction.min_bid;
+        if let Some(reserved_buyer) = &auction.reserved_buyer {
+            require!(
+                buyer == reserved_buyer,
+                "This listing is reserved for another buyer!"
+            );
+        }
+        if let Some(merkle_root) = &auction.merkle_root {
+            require!(
+                self.verify_merkle_proof(merkle_root, buyer, &proof.unwrap_or_default()),
+                "The buyer is not part of the allowlist!"
+            );
+        }
 
         let wegld = self.wrapping_token().get();
         let require_swap = swaps.is_some() && auction.payment_token_type != payment_token;
@@ -206,7 +239,12 @@ pub trait CommonModule:
                 &payments,
             );
             let wrapping = self.require_egld_conversion(&auction, &payment_token, &wegld);
-            self.distribute_tokens(&auction, Option::Some(&buy_amount), wrapping);
+            self.distribute_tokens(
+                &auction,
+                Option::Some(&buy_amount),
+                wrapping,
+                referrer.as_ref(),
+            );
         } else {
             let steps = swaps.into_option().unwrap();
             self.freezed_auctions().insert(auction_id);
@@ -229,15 +267,31 @@ pub trait CommonModule:
         auction: &Auction<Self::Api>,
         opt_sft_amount: Option<&BigUint>,
         wrapping: bool,
+        referrer: Option<&ManagedAddress>,
     ) {
         if !auction.current_winner.is_zero() {
             let nft_info =
                 self.get_nft_info(&auction.auctioned_token_type, auction.auctioned_token_nonce);
 
-            let bid_split_amounts = self.calculate_amount_split(
+            let mut config = self.get_collection_config(&auction.auctioned_token_type);
+            // Bundle royalties were already bounded item by item when listed
+            if !auction.bundle_items.is_empty() {
+                if let Some(config) = config.as_mut() {
+                    config.custom_royalties = false;
+                }
+            }
+            let mut bid_split_amounts = self.calculate_amount_split(
                 &auction.current_bid,
                 &auction.creator_royalties_percentage,
-                self.get_collection_config(&auction.auctioned_token_type),
+                &auction.marketplace_cut_percentage,
+                config,
+                &auction.original_owner,
+            );
+            self.apply_referral(
+                &mut bid_split_amounts,
+                referrer,
+                &auction.current_winner,
+                &auction.original_owner,
             );
 
             // send NFT to auction winner
@@ -252,12 +306,17 @@ pub trait CommonModule:
                 _ => &auction.nr_auctioned_tokens,
             };
 
-            self.distribute_tokens_common(
+            let nfts = if auction.bundle_items.is_empty() {
                 ManagedVec::from(EsdtTokenPayment::new(
                     auction.auctioned_token_type.clone(),
                     auction.auctioned_token_nonce,
                     nft_amount_to_send.clone(),
-                )),
+                ))
+            } else {
+                auction.bundle_items.clone()
+            };
+            self.distribute_tokens_common(
+                nfts,
                 &auction.payment_token_type,
                 auction.payment_token_nonce,
                 &nft_info.creator,
@@ -282,6 +341,17 @@ pub trait CommonModule:
     }
 
     fn return_auction_nft(&self, auction: &Auction<Self::Api>) {
+        if !auction.bundle_items.is_empty() {
+            for item in auction.bundle_items.iter() {
+                self.transfer_or_save_payment(
+                    &auction.original_owner,
+                    &EgldOrEsdtTokenIdentifier::esdt(item.token_identifier.clone()),
+                    item.token_nonce,
+                    &item.amount,
+                );
+            }
+            return;
+        }
         self.transfer_or_save_payment(
             &auction.original_owner,
             &EgldOrEsdtTokenIdentifier::esdt(auction.auctioned_token_type.clone()),
@@ -330,6 +400,14 @@ pub trait CommonModule:
                 &offer.price,
             );
         }
+        if offer.committed {
+            self.release_funds(
+                &offer.owner,
+                &offer.payment_token,
+                offer.payment_nonce,
+                &(&offer.price * &offer.quantity),
+            );
+        }
         self.user_collection_global_offers(&offer.owner, &offer.collection)
             .swap_remove(&offer.offer_id);
         self.collection_global_offers(&offer.collection)
@@ -363,6 +441,10 @@ pub trait CommonModule:
             auction.auction_type == AuctionType::Nft,
             "Cannot accept or decline offers for auctions, just for listings with a fixed price!"
         );
+        require!(
+            auction.bundle_items.is_empty(),
+            "Offers can't be accepted for bundle listings!"
+        );
 
         require!(
             auction.nr_auctioned_tokens == offer.quantity,
@@ -384,6 +466,14 @@ pub trait CommonModule:
     }
 
     fn common_offer_remove(&self, offer_id: u64, offer: &Offer<Self::Api>) {
+        if offer.committed {
+            self.release_funds(
+                &offer.offer_owner,
+                &offer.payment_token_type,
+                offer.payment_token_nonce,
+                &offer.price,
+            );
+        }
         self.check_offer_sent(
             &offer.offer_owner,
             &offer.token_type,
@@ -420,6 +510,8 @@ pub trait CommonModule:
         {
             self.distribute_rewards(new_owner, original_owner);
         }
+        self.record_trade_volume(original_owner, payment_token_id, &total_amount);
+        self.record_trade_volume(new_owner, payment_token_id, &total_amount);
 
         if wrapping {
             if payment_token_id.is_egld() {
@@ -448,6 +540,32 @@ pub trait CommonModule:
             &bid_split_amounts.seller,
         );
 
+        if bid_split_amounts.reverse_royalties {
+            self.transfer_or_save_payment(
+                new_owner,
+                payment_token_id,
+                payment_token_nonce,
+                &bid_split_amounts.creator,
+            );
+        } else if nfts.len() > 1 {
+            self.share_bundle_royalties(
+                &nfts,
+                creator,
+                payment_token_id,
+                &bid_split_amounts.creator,
+                payment_token_nonce,
+            );
+        } else {
+            // send part as royalties to creator
+            self.share_royalties(
+                creator,
+                &bid_split_amounts.royalty_splits,
+                payment_token_id,
+                bid_split_amounts.creator.clone(),
+                payment_token_nonce,
+            );
+        }
+
         // send NFT to new owner
         self.tx().to(new_owner).multi_esdt(nfts).transfer_execute();
 
@@ -458,27 +576,41 @@ pub trait CommonModule:
                 payment_token_nonce,
                 &bid_split_amounts.marketplace,
             );
-        } else {
-            self.share_marketplace_fees(
+        } else if let Some(xoxno_fee) = self.take_xoxno_fee(
+            new_owner,
+            payment_token_id,
+            &(&bid_split_amounts.marketplace - &bid_split_amounts.referral),
+        ) {
+            // Buyer settled the fee net of the referral in XOXNO and gets that part refunded
+            self.share_referral_fees(
+                &bid_split_amounts.referrer,
                 payment_token_id,
-                bid_split_amounts.marketplace.clone(),
+                &bid_split_amounts.referral,
                 payment_token_nonce,
+                1,
             );
-        }
-
-        if bid_split_amounts.reverse_royalties {
             self.transfer_or_save_payment(
                 new_owner,
                 payment_token_id,
                 payment_token_nonce,
-                &bid_split_amounts.creator,
+                &(&bid_split_amounts.marketplace - &bid_split_amounts.referral),
+            );
+            self.share_marketplace_fees(
+                &EgldOrEsdtTokenIdentifier::esdt(self.xoxno_token().get()),
+                xoxno_fee,
+                0,
             );
         } else {
-            // send part as royalties to creator
-            self.share_royalties(
-                creator,
+            self.share_referral_fees(
+                &bid_split_amounts.referrer,
                 payment_token_id,
-                bid_split_amounts.creator.clone(),
+                &bid_split_amounts.referral,
+                payment_token_nonce,
+                1,
+            );
+            self.share_marketplace_fees(
+                payment_token_id,
+                &bid_split_amounts.marketplace - &bid_split_amounts.referral,
                 payment_token_nonce,
             );
         }
@@ -499,6 +631,8 @@ pub trait CommonModule:
         if total_amount >= MIN_TRADE_REWARD && payment_token_id.is_egld() {
             self.distribute_rewards(new_owner, original_owner);
         }
+        self.record_trade_volume(original_owner, payment_token_id, &total_amount);
+        self.record_trade_volume(new_owner, payment_token_id, &total_amount);
 
         if wrapping {
             if payment_token_id.is_egld() {
@@ -532,6 +666,7 @@ pub trait CommonModule:
             // send part as royalties to creator
             self.share_royalties(
                 creator,
+                &bid_split_amounts.royalty_splits,
                 payment_token_id,
                 bid_split_amounts.creator.clone(),
                 payment_token_nonce,
@@ -568,19 +703,129 @@ pub trait CommonModule:
         }
     }
 
+    fn share_referral_fees(
+        &self,
+        referrer: &ManagedAddress,
+        payment_token_id: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+        payment_token_nonce: u64,
+        trades: u64,
+    ) {
+        if amount > &BigUint::zero() {
+            self.transfer_or_save_payment(referrer, payment_token_id, payment_token_nonce, amount);
+            self.record_referral(referrer, payment_token_id, amount, trades);
+        }
+    }
+
     fn share_royalties(
         &self,
         creator: &ManagedAddress,
+        royalty_splits: &ManagedVec<RoyaltySplit<Self::Api>>,
         payment_token_id: &EgldOrEsdtTokenIdentifier,
         amount: BigUint,
         payment_token_nonce: u64,
     ) {
-        if amount > BigUint::zero() {
+        if amount == BigUint::zero() {
+            return;
+        }
+        if royalty_splits.is_empty() {
+            self.deposit_royalties(creator, payment_token_id, &amount, payment_token_nonce);
+            return;
+        }
+        let mut remaining = amount.clone();
+        let last_index = royalty_splits.len() - 1;
+        for (index, split) in royalty_splits.iter().enumerate() {
+            // The last recipient takes the rounding dust
+            let share_amount = if index == last_index {
+                remaining.clone()
+            } else {
+                &amount * &split.share / PERCENTAGE_TOTAL
+            };
+            remaining -= &share_amount;
+            self.deposit_royalties(
+                &split.address,
+                payment_token_id,
+                &share_amount,
+                payment_token_nonce,
+            );
+        }
+    }
+
+    // Each item of a bundle gets a share weighted by its own royalty and amount
+    fn share_bundle_royalties(
+        &self,
+        nfts: &ManagedVec<EsdtTokenPayment>,
+        primary_creator: &ManagedAddress,
+        payment_token_id: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+        payment_token_nonce: u64,
+    ) {
+        if amount == &BigUint::zero() {
+            return;
+        }
+        let mut weights = ManagedVec::<Self::Api, BigUint>::new();
+        let mut total_weight = BigUint::zero();
+        for item in nfts.iter() {
+            let weight = self.bundle_item_royalties(&item, &BigUint::zero()) * &item.amount;
+            total_weight += &weight;
+            weights.push(weight);
+        }
+        if total_weight == 0 {
+            self.deposit_royalties(
+                primary_creator,
+                payment_token_id,
+                amount,
+                payment_token_nonce,
+            );
+            return;
+        }
+        let mut remaining = amount.clone();
+        let last_index = nfts.len() - 1;
+        for (index, item) in nfts.iter().enumerate() {
+            // The last item takes the rounding dust
+            let item_amount = if index == last_index {
+                remaining.clone()
+            } else {
+                amount * &*weights.get(index) / &total_weight
+            };
+            remaining -= &item_amount;
+            let creator = self
+                .get_nft_info(&item.token_identifier, item.token_nonce)
+                .creator;
+            let royalty_splits = self
+                .get_collection_config(&item.token_identifier)
+                .map(|config| config.royalty_splits)
+                .unwrap_or_default();
+            self.share_royalties(
+                &creator,
+                &royalty_splits,
+                payment_token_id,
+                item_amount,
+                payment_token_nonce,
+            );
+        }
+    }
+
+    fn bundle_item_royalties(&self, item: &EsdtTokenPayment, cut_percentage: &BigUint) -> BigUint {
+        let royalties = self
+            .get_nft_info(&item.token_identifier, item.token_nonce)
+            .royalties;
+        self.cap_royalties(&item.token_identifier, royalties, cut_percentage)
+    }
+
+    fn deposit_royalties(
+        &self,
+        creator: &ManagedAddress,
+        payment_token_id: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+        payment_token_nonce: u64,
+    ) {
+        if amount > &BigUint::zero() {
             self.tx()
                 .to(self.accumulator().get())
                 .typed(accumulator::AccumulatorProxy)
                 .deposit_royalties(creator)
-                .egld_or_single_esdt(payment_token_id, payment_token_nonce, &amount)
+                .egld_or_single_esdt(payment_token_id, payment_token_nonce, amount)
                 .gas(5_500_000)
                 .transfer_execute();
         }
@@ -696,7 +941,7 @@ pub trait CommonModule:
                     },
                     &original_payment,
                 );
-                self.distribute_tokens(&auction, Option::Some(quantity), wrapping);
+                self.distribute_tokens(&auction, Option::Some(quantity), wrapping, None);
             } else {
                 self.tx().to(paid_by).payment(&payment).transfer_execute();
             }
@@ -1,10 +1,15 @@
-use crate::{CollectionExtraFeesConfig, CollectionFeeConfig};
+use crate::{
+    CollectionExtraFeesConfig, CollectionFeeConfig, GovernanceAction, Role, RoyaltySplit,
+    PERCENTAGE_TOTAL,
+};
 
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
 // Maximum extra fee that can be set (20% = 2000 basis points)
-const MAX_EXTRA_FEE: u64 = 2_000;
+pub const MAX_EXTRA_FEE: u64 = 2_000;
+// Maximum number of royalty recipients per collection
+const MAX_ROYALTY_SPLITS: usize = 10;
 
 #[multiversx_sc::module]
 pub trait CreatorModule:
@@ -13,10 +18,13 @@ pub trait CreatorModule:
     + crate::views::ViewsModule
     + crate::events::EventsModule
     + crate::wrapping::WrappingModule
+    + crate::fees::FeesModule
+    + crate::claims::ClaimsModule
+    + crate::governance::GovernanceModule
 {
     #[endpoint(setCutFeesReverted)]
     fn set_cut_fees_reverted(&self, token_id: &TokenIdentifier, value: bool) {
-        self.require_admin(None);
+        self.require_role(Role::FeeManager, None);
         let config_map = self.collection_config(token_id);
         if config_map.is_empty() {
             config_map.set(CollectionFeeConfig {
@@ -30,6 +38,7 @@ pub trait CreatorModule:
                     address: ManagedAddress::zero(),
                 },
                 admin: ManagedAddress::zero(),
+                royalty_splits: ManagedVec::new(),
             });
         } else {
             config_map.update(|f| {
@@ -43,7 +52,7 @@ pub trait CreatorModule:
     fn set_royalties_reverted(&self, token_id: &TokenIdentifier, value: bool) {
         let config_map = self.collection_config(token_id);
         if config_map.is_empty() {
-            self.require_admin(None);
+            self.require_role(Role::FeeManager, None);
             config_map.set(CollectionFeeConfig {
                 reverse_cut_fees: false,
                 reverse_royalties: value,
@@ -55,9 +64,10 @@ pub trait CreatorModule:
                     address: ManagedAddress::zero(),
                 },
                 admin: ManagedAddress::zero(),
+                royalty_splits: ManagedVec::new(),
             });
         } else {
-            self.require_admin(Some(config_map.get().admin));
+            self.require_role(Role::FeeManager, Some(config_map.get().admin));
             config_map.update(|f| {
                 f.reverse_royalties = value;
             })
@@ -67,53 +77,84 @@ pub trait CreatorModule:
 
     #[endpoint(setExtraFees)]
     fn set_extra_fees(&self, token_id: &TokenIdentifier, amount: BigUint, address: ManagedAddress) {
-        // SECURITY FIX: Validate extra fee doesn't exceed maximum (20%)
-        require!(
-            amount <= MAX_EXTRA_FEE,
-            "Extra fee cannot exceed 20%!"
-        );
+        let action = GovernanceAction::SetExtraFees(token_id.clone(), amount, address);
+        self.require_action_role(&action);
+        self.queue_action(action);
+    }
 
+    #[endpoint(setCustomRoyalties)]
+    fn set_custom_royalties(
+        &self,
+        token_id: &TokenIdentifier,
+        min: BigUint,
+        max: BigUint,
+        enabled: bool,
+    ) {
         let config_map = self.collection_config(token_id);
+        require!(
+            min <= max,
+            "Min royalties must be lower or equal than max royalties!"
+        );
         if config_map.is_empty() {
-            self.require_admin(None);
+            self.require_role(Role::FeeManager, None);
             config_map.set(CollectionFeeConfig {
                 reverse_cut_fees: false,
                 reverse_royalties: false,
-                custom_royalties: false,
+                custom_royalties: enabled,
                 min_royalties: BigUint::zero(),
                 max_royalties: BigUint::zero(),
-                extra_fees: CollectionExtraFeesConfig { amount, address },
+                extra_fees: CollectionExtraFeesConfig {
+                    amount: BigUint::zero(),
+                    address: ManagedAddress::zero(),
+                },
                 admin: ManagedAddress::zero(),
+                royalty_splits: ManagedVec::new(),
             });
         } else {
-            self.require_admin(Some(config_map.get().admin));
+            self.require_role(Role::FeeManager, Some(config_map.get().admin));
             config_map.update(|f| {
-                f.extra_fees.amount = amount;
-                f.extra_fees.address = address;
+                f.min_royalties = min;
+                f.max_royalties = max;
+                f.custom_royalties = enabled;
             })
         }
         self.emit_collection_config(token_id, &config_map.get());
     }
 
-    #[endpoint(setCustomRoyalties)]
-    fn set_custom_royalties(
+    #[endpoint(setRoyaltySplits)]
+    fn set_royalty_splits(
         &self,
         token_id: &TokenIdentifier,
-        min: BigUint,
-        max: BigUint,
-        enabled: bool,
+        splits: MultiValueEncoded<RoyaltySplit<Self::Api>>,
     ) {
-        let config_map = self.collection_config(token_id);
+        let splits = splits.to_vec();
         require!(
-            min <= max,
-            "Min royalties must be lower or equal than max royalties!"
+            splits.len() <= MAX_ROYALTY_SPLITS,
+            "Cannot split royalties between more than 10 addresses!"
         );
+        if !splits.is_empty() {
+            let mut total_share = BigUint::zero();
+            for split in splits.iter() {
+                require!(!split.address.is_zero(), "Invalid royalty split address!");
+                require!(
+                    split.share > 0,
+                    "Royalty split share must be higher than 0!"
+                );
+                total_share += &split.share;
+            }
+            require!(
+                total_share == PERCENTAGE_TOTAL,
+                "Royalty split shares must add up to 10,000!"
+            );
+        }
+
+        let config_map = self.collection_config(token_id);
         if config_map.is_empty() {
-            self.require_admin(None);
+            self.require_role(Role::FeeManager, None);
             config_map.set(CollectionFeeConfig {
                 reverse_cut_fees: false,
                 reverse_royalties: false,
-                custom_royalties: enabled,
+                custom_royalties: false,
                 min_royalties: BigUint::zero(),
                 max_royalties: BigUint::zero(),
                 extra_fees: CollectionExtraFeesConfig {
@@ -121,13 +162,12 @@ pub trait CreatorModule:
                     address: ManagedAddress::zero(),
                 },
                 admin: ManagedAddress::zero(),
+                royalty_splits: splits,
             });
         } else {
-            self.require_admin(Some(config_map.get().admin));
+            self.require_role(Role::FeeManager, Some(config_map.get().admin));
             config_map.update(|f| {
-                f.min_royalties = min;
-                f.max_royalties = max;
-                f.custom_royalties = enabled;
+                f.royalty_splits = splits;
             })
         }
         self.emit_collection_config(token_id, &config_map.get());
@@ -135,7 +175,7 @@ pub trait CreatorModule:
 
     #[endpoint(setConfigAdmin)]
     fn set_config_admin(&self, token_id: &TokenIdentifier, admin: ManagedAddress) {
-        self.require_admin(None);
+        self.require_role(Role::FeeManager, None);
         let config_map = self.collection_config(token_id);
         if config_map.is_empty() {
             config_map.set(CollectionFeeConfig {
@@ -149,6 +189,7 @@ pub trait CreatorModule:
                     address: ManagedAddress::zero(),
                 },
                 admin,
+                royalty_splits: ManagedVec::new(),
             });
         } else {
             config_map.update(|f| {
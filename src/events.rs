@@ -1,7 +1,13 @@
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
-use crate::{auction::GlobalOffer, CollectionFeeConfig};
+use crate::{
+    auction::{
+        BlacklistEntry, CreatorSource, Feature, FeeTier, GlobalOffer, NftSwap, QueuedAction,
+        Raffle, Rental, Role, SignedOffer, SignedOrder,
+    },
+    CollectionFeeConfig,
+};
 
 use super::auction::{Auction, AuctionType, Offer, OfferStatus};
 
@@ -378,4 +384,254 @@ pub trait EventsModule {
         #[indexed] collection: &TokenIdentifier,
         #[indexed] config: &CollectionFeeConfig<Self::Api>,
     );
+
+    #[event("yield_mode")]
+    fn emit_yield_mode_event(&self, #[indexed] owner: &ManagedAddress, #[indexed] enabled: bool);
+
+    #[event("yield_shares")]
+    fn emit_yield_shares_event(
+        &self,
+        #[indexed] owner: &ManagedAddress,
+        #[indexed] shares: &BigUint,
+    );
+
+    #[event("claim")]
+    fn emit_claim_event(
+        &self,
+        #[indexed] owner: &ManagedAddress,
+        #[indexed] receiver: &ManagedAddress,
+        #[indexed] payment: &EgldOrEsdtTokenPayment,
+    );
+
+    #[event("claim_receiver")]
+    fn emit_claim_receiver_event(
+        &self,
+        #[indexed] owner: &ManagedAddress,
+        #[indexed] receiver: &ManagedAddress,
+    );
+
+    #[event("fee_tiers")]
+    fn emit_fee_tiers_event(
+        &self,
+        #[indexed] window_epochs: u64,
+        #[indexed] tiers: &ManagedVec<FeeTier<Self::Api>>,
+    );
+
+    #[event("xoxno_lock")]
+    fn emit_xoxno_lock_event(&self, #[indexed] owner: &ManagedAddress, #[indexed] locked: &BigUint);
+
+    #[event("token_fee")]
+    fn emit_token_fee_event(
+        &self,
+        #[indexed] payment_token: &EgldOrEsdtTokenIdentifier,
+        #[indexed] cut_percentage: &BigUint,
+    );
+
+    #[event("referrer")]
+    fn emit_referrer_event(&self, #[indexed] referrer: &ManagedAddress, #[indexed] share: &BigUint);
+
+    #[event("creator_source")]
+    fn emit_creator_source_event(
+        &self,
+        #[indexed] collection: &TokenIdentifier,
+        #[indexed] source: OptionalValue<CreatorSource<Self::Api>>,
+    );
+
+    #[event("action_queued")]
+    fn emit_action_queued_event(
+        &self,
+        #[indexed] action_id: u64,
+        #[indexed] action: &QueuedAction<Self::Api>,
+    );
+
+    #[event("action_executed")]
+    fn emit_action_executed_event(&self, #[indexed] action_id: u64);
+
+    #[event("action_cancelled")]
+    fn emit_action_cancelled_event(&self, #[indexed] action_id: u64);
+
+    #[event("role_granted")]
+    fn emit_role_granted_event(&self, #[indexed] role: Role, #[indexed] address: &ManagedAddress);
+
+    #[event("role_revoked")]
+    fn emit_role_revoked_event(&self, #[indexed] role: Role, #[indexed] address: &ManagedAddress);
+
+    #[event("feature_pause")]
+    fn emit_feature_pause_event(&self, #[indexed] feature: Feature, #[indexed] paused: bool);
+
+    #[event("collection_halt")]
+    fn emit_collection_halt_event(
+        &self,
+        #[indexed] collection: &TokenIdentifier,
+        #[indexed] halted: bool,
+    );
+
+    #[event("blacklist")]
+    fn emit_blacklist_event(
+        &self,
+        #[indexed] wallet: &ManagedAddress,
+        #[indexed] entry: &BlacklistEntry,
+    );
+
+    #[event("escrow")]
+    fn emit_escrow_event(
+        &self,
+        #[indexed] seller: &ManagedAddress,
+        #[indexed] collection: &TokenIdentifier,
+        #[indexed] nonce: u64,
+        #[indexed] balance: &BigUint,
+    );
+
+    #[event("order_fill")]
+    fn emit_order_fill_event(
+        &self,
+        #[indexed] order: &SignedOrder<Self::Api>,
+        #[indexed] buyer: &ManagedAddress,
+    );
+
+    #[event("order_cancel")]
+    fn emit_order_cancel_event(
+        &self,
+        #[indexed] seller: &ManagedAddress,
+        #[indexed] order_nonce: u64,
+    );
+
+    #[event("order_invalidate")]
+    fn emit_order_invalidate_event(
+        &self,
+        #[indexed] seller: &ManagedAddress,
+        #[indexed] min_order_nonce: u64,
+    );
+
+    #[event("signed_offer_accept")]
+    fn emit_signed_offer_accept_event(
+        &self,
+        #[indexed] offer: &SignedOffer<Self::Api>,
+        #[indexed] seller: &ManagedAddress,
+        #[indexed] nonce: u64,
+    );
+
+    #[event("signed_offer_cancel")]
+    fn emit_signed_offer_cancel_event(
+        &self,
+        #[indexed] buyer: &ManagedAddress,
+        #[indexed] offer_hash: &ManagedByteArray<32>,
+    );
+
+    #[event("signed_offer_counter")]
+    fn emit_signed_offer_counter_event(
+        &self,
+        #[indexed] buyer: &ManagedAddress,
+        #[indexed] counter: u64,
+    );
+
+    #[event("swap_create")]
+    fn emit_swap_create_event(&self, #[indexed] swap_id: u64, #[indexed] swap: &NftSwap<Self::Api>);
+
+    #[event("swap_fill")]
+    fn emit_swap_fill_event(
+        &self,
+        #[indexed] swap_id: u64,
+        #[indexed] swap: &NftSwap<Self::Api>,
+        #[indexed] taker: &ManagedAddress,
+        #[indexed] received: &ManagedVec<EsdtTokenPayment<Self::Api>>,
+    );
+
+    #[event("swap_cancel")]
+    fn emit_swap_cancel_event(&self, #[indexed] swap_id: u64, #[indexed] swap: &NftSwap<Self::Api>);
+
+    #[event("rental_list")]
+    fn emit_rental_list_event(
+        &self,
+        #[indexed] rental_id: u64,
+        #[indexed] rental: &Rental<Self::Api>,
+    );
+
+    #[event("rental_rent")]
+    fn emit_rental_rent_event(
+        &self,
+        #[indexed] rental_id: u64,
+        #[indexed] rental: &Rental<Self::Api>,
+        #[indexed] days: u64,
+    );
+
+    #[event("rental_return")]
+    fn emit_rental_return_event(
+        &self,
+        #[indexed] rental_id: u64,
+        #[indexed] rental: &Rental<Self::Api>,
+    );
+
+    #[event("rental_forfeit")]
+    fn emit_rental_forfeit_event(
+        &self,
+        #[indexed] rental_id: u64,
+        #[indexed] rental: &Rental<Self::Api>,
+    );
+
+    #[event("rental_cancel")]
+    fn emit_rental_cancel_event(
+        &self,
+        #[indexed] rental_id: u64,
+        #[indexed] rental: &Rental<Self::Api>,
+    );
+
+    #[event("raffle_create")]
+    fn emit_raffle_create_event(
+        &self,
+        #[indexed] raffle_id: u64,
+        #[indexed] raffle: &Raffle<Self::Api>,
+    );
+
+    #[event("raffle_tickets")]
+    fn emit_raffle_tickets_event(
+        &self,
+        #[indexed] raffle_id: u64,
+        #[indexed] buyer: &ManagedAddress,
+        #[indexed] tickets: u64,
+        #[indexed] tickets_sold: u64,
+    );
+
+    #[event("raffle_draw")]
+    fn emit_raffle_draw_event(
+        &self,
+        #[indexed] raffle_id: u64,
+        #[indexed] raffle: &Raffle<Self::Api>,
+        #[indexed] winning_ticket: u64,
+    );
+
+    #[event("raffle_refund")]
+    fn emit_raffle_refund_event(
+        &self,
+        #[indexed] raffle_id: u64,
+        #[indexed] buyer: &ManagedAddress,
+        #[indexed] tickets: u64,
+    );
+
+    #[event("yield_buffer")]
+    fn emit_yield_buffer_event(&self, #[indexed] amount: &BigUint);
+
+    #[event("raffle_fail")]
+    fn emit_raffle_fail_event(
+        &self,
+        #[indexed] raffle_id: u64,
+        #[indexed] raffle: &Raffle<Self::Api>,
+    );
+
+    #[event("swap_royalty")]
+    fn emit_swap_royalty_event(
+        &self,
+        #[indexed] collection: &TokenIdentifier,
+        #[indexed] amount: &BigUint,
+    );
+
+    #[event("bid_refund")]
+    fn emit_bid_refund_event(
+        &self,
+        #[indexed] auction_id: u64,
+        #[indexed] bidder: &ManagedAddress,
+        #[indexed] payment_token: &EgldOrEsdtTokenIdentifier,
+        #[indexed] payment_nonce: u64,
+        #[indexed] amount: &BigUint,
+    );
 }
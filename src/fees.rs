@@ -0,0 +1,282 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::{
+    auction::{FeeTier, UserFeeTier, VolumeBucket},
+    PERCENTAGE_TOTAL,
+};
+
+// XOXNO fee rates are expressed as XOXNO units per 10^18 units of the payment token
+const XOXNO_RATE_PRECISION: u64 = 1_000_000_000_000_000_000;
+
+#[multiversx_sc::module]
+pub trait FeesModule: crate::storage::StorageModule + crate::events::EventsModule {
+    #[payable("*")]
+    #[endpoint(lockXoxno)]
+    fn lock_xoxno(&self) {
+        let payment = self.call_value().single_esdt();
+        require!(
+            !self.xoxno_token().is_empty() && payment.token_identifier == self.xoxno_token().get(),
+            "Only XOXNO can be locked!"
+        );
+        let caller = self.blockchain().get_caller();
+        let map_locked = self.xoxno_locked(&caller);
+        map_locked.update(|amt| *amt += &payment.amount);
+        // Every top up restarts the lock so a discount cannot be borrowed for a single trade
+        self.xoxno_unlock_time(&caller)
+            .set(self.current_timestamp() + self.xoxno_lock_period().get());
+        self.emit_xoxno_lock_event(&caller, &map_locked.get());
+    }
+
+    #[endpoint(unlockXoxno)]
+    fn unlock_xoxno(&self, amount: BigUint) {
+        let caller = self.blockchain().get_caller();
+        require!(
+            self.current_timestamp() >= self.xoxno_unlock_time(&caller).get(),
+            "Your XOXNO is still locked!"
+        );
+        let map_locked = self.xoxno_locked(&caller);
+        require!(
+            map_locked.get() >= amount,
+            "Your locked balance is under the requested amount!"
+        );
+        map_locked.update(|amt| *amt -= &amount);
+        self.tx()
+            .to(&caller)
+            .single_esdt(&self.xoxno_token().get(), 0, &amount)
+            .transfer();
+        self.emit_xoxno_lock_event(&caller, &map_locked.get());
+    }
+
+    #[endpoint(setPayFeesInXoxno)]
+    fn set_pay_fees_in_xoxno(&self, enabled: bool) {
+        let caller = self.blockchain().get_caller();
+        self.pay_fees_in_xoxno(&caller).set(enabled);
+    }
+
+    fn get_marketplace_cut(&self, payment_token: &EgldOrEsdtTokenIdentifier) -> BigUint {
+        let map_token_cut = self.token_cut_percentage(payment_token);
+        if map_token_cut.is_empty() {
+            self.bid_cut_percentage().get()
+        } else {
+            map_token_cut.get()
+        }
+    }
+
+    #[view(getEffectiveFee)]
+    fn get_effective_fee(&self, payment_token: EgldOrEsdtTokenIdentifier) -> BigUint {
+        self.get_marketplace_cut(&payment_token)
+    }
+
+    #[view(getEffectiveFees)]
+    fn get_effective_fees(
+        &self,
+    ) -> MultiValueEncoded<MultiValue2<EgldOrEsdtTokenIdentifier, BigUint>> {
+        let mut results = MultiValueEncoded::new();
+        for token in self.accepted_tokens().iter() {
+            let fee = self.get_marketplace_cut(&token);
+            results.push((token, fee).into());
+        }
+        results
+    }
+
+    fn apply_fee_discounts(&self, seller: &ManagedAddress, cut_percentage: BigUint) -> BigUint {
+        let mut cut = cut_percentage;
+        let tier_discount = self.get_user_fee_tier(seller.clone()).discount;
+        if tier_discount > 0 {
+            cut = cut * (BigUint::from(PERCENTAGE_TOTAL) - tier_discount) / PERCENTAGE_TOTAL;
+        }
+        let threshold = self.xoxno_holder_threshold().get();
+        // Only locked XOXNO counts, a wallet balance can be borrowed for a single trade
+        if threshold > 0 && self.xoxno_locked(seller).get() >= threshold {
+            let holder_discount = self.xoxno_holder_discount().get();
+            cut = cut * (BigUint::from(PERCENTAGE_TOTAL) - holder_discount) / PERCENTAGE_TOTAL;
+        }
+        cut
+    }
+
+    fn current_timestamp(&self) -> u64 {
+        self.blockchain()
+            .get_block_timestamp_seconds()
+            .as_u64_seconds()
+    }
+
+    // Charges the discounted XOXNO equivalent of `marketplace_fee` from the buyer's locked balance
+    fn take_xoxno_fee(
+        &self,
+        buyer: &ManagedAddress,
+        payment_token: &EgldOrEsdtTokenIdentifier,
+        marketplace_fee: &BigUint,
+    ) -> Option<BigUint> {
+        if marketplace_fee == &0 || !self.pay_fees_in_xoxno(buyer).get() {
+            return None;
+        }
+        let rate = self.xoxno_fee_rate(payment_token).get();
+        if rate == 0 {
+            return None;
+        }
+        // A stale rate falls back to charging the fee in the payment token
+        let max_age = self.xoxno_rate_max_age().get();
+        if max_age > 0
+            && self.xoxno_fee_rate_updated(payment_token).get() + max_age < self.current_timestamp()
+        {
+            return None;
+        }
+        let discount = self.xoxno_fee_discount().get();
+        let xoxno_fee = marketplace_fee * &rate / XOXNO_RATE_PRECISION
+            * (BigUint::from(PERCENTAGE_TOTAL) - discount)
+            / PERCENTAGE_TOTAL;
+        let map_locked = self.xoxno_locked(buyer);
+        if xoxno_fee == 0 || map_locked.get() < xoxno_fee {
+            return None;
+        }
+        map_locked.update(|amt| *amt -= &xoxno_fee);
+        self.emit_xoxno_lock_event(buyer, &map_locked.get());
+        Some(xoxno_fee)
+    }
+
+    // Only EGLD denominated trades (EGLD or WEGLD) count towards the rolling volume
+    fn record_trade_volume(
+        &self,
+        user: &ManagedAddress,
+        payment_token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+    ) {
+        let window = self.volume_window_epochs().get();
+        if window == 0 || amount == &0 {
+            return;
+        }
+        if !payment_token.is_egld()
+            && payment_token != &EgldOrEsdtTokenIdentifier::esdt(self.wrapping_token().get())
+        {
+            return;
+        }
+
+        let current_epoch = self.blockchain().get_block_epoch();
+        let map_volume = self.user_trade_volume(user);
+        let mut buckets = ManagedVec::new();
+        let mut found = false;
+        for bucket in map_volume.get().iter() {
+            if bucket.epoch + window <= current_epoch {
+                continue;
+            }
+            if bucket.epoch == current_epoch {
+                found = true;
+                buckets.push(VolumeBucket {
+                    epoch: bucket.epoch,
+                    volume: &bucket.volume + amount,
+                });
+            } else {
+                buckets.push(bucket.clone());
+            }
+        }
+        if !found {
+            buckets.push(VolumeBucket {
+                epoch: current_epoch,
+                volume: amount.clone(),
+            });
+        }
+        map_volume.set(buckets);
+    }
+
+    fn get_rolling_volume(&self, user: &ManagedAddress) -> BigUint {
+        let window = self.volume_window_epochs().get();
+        let current_epoch = self.blockchain().get_block_epoch();
+        let mut volume = BigUint::zero();
+        for bucket in self.user_trade_volume(user).get().iter() {
+            if bucket.epoch + window > current_epoch {
+                volume += &bucket.volume;
+            }
+        }
+        volume
+    }
+
+    #[view(getUserFeeTier)]
+    fn get_user_fee_tier(&self, user: ManagedAddress) -> UserFeeTier<Self::Api> {
+        let volume = self.get_rolling_volume(&user);
+        let mut result = UserFeeTier {
+            tier: 0,
+            volume: volume.clone(),
+            discount: BigUint::zero(),
+        };
+        for (index, tier) in self.fee_tiers().get().iter().enumerate() {
+            if volume < tier.min_volume {
+                break;
+            }
+            result.tier = index + 1;
+            result.discount = tier.discount.clone();
+        }
+        result
+    }
+
+    #[view(getTokenCutPercentage)]
+    #[storage_mapper("tokenCutPercentage")]
+    fn token_cut_percentage(
+        &self,
+        payment_token: &EgldOrEsdtTokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[view(getFeeOverrideTokens)]
+    #[storage_mapper("feeOverrideTokens")]
+    fn fee_override_tokens(&self) -> UnorderedSetMapper<EgldOrEsdtTokenIdentifier>;
+
+    #[view(getXoxnoLocked)]
+    #[storage_mapper("xoxnoLocked")]
+    fn xoxno_locked(&self, user: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[view(isPayingFeesInXoxno)]
+    #[storage_mapper("payFeesInXoxno")]
+    fn pay_fees_in_xoxno(&self, user: &ManagedAddress) -> SingleValueMapper<bool>;
+
+    #[view(getXoxnoHolderThreshold)]
+    #[storage_mapper("xoxnoHolderThreshold")]
+    fn xoxno_holder_threshold(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getXoxnoHolderDiscount)]
+    #[storage_mapper("xoxnoHolderDiscount")]
+    fn xoxno_holder_discount(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getXoxnoFeeRate)]
+    #[storage_mapper("xoxnoFeeRate")]
+    fn xoxno_fee_rate(
+        &self,
+        payment_token: &EgldOrEsdtTokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[view(getXoxnoFeeRateUpdated)]
+    #[storage_mapper("xoxnoFeeRateUpdated")]
+    fn xoxno_fee_rate_updated(
+        &self,
+        payment_token: &EgldOrEsdtTokenIdentifier,
+    ) -> SingleValueMapper<u64>;
+
+    #[view(getXoxnoRateMaxAge)]
+    #[storage_mapper("xoxnoRateMaxAge")]
+    fn xoxno_rate_max_age(&self) -> SingleValueMapper<u64>;
+
+    #[view(getXoxnoLockPeriod)]
+    #[storage_mapper("xoxnoLockPeriod")]
+    fn xoxno_lock_period(&self) -> SingleValueMapper<u64>;
+
+    #[view(getXoxnoUnlockTime)]
+    #[storage_mapper("xoxnoUnlockTime")]
+    fn xoxno_unlock_time(&self, user: &ManagedAddress) -> SingleValueMapper<u64>;
+
+    #[view(getXoxnoFeeDiscount)]
+    #[storage_mapper("xoxnoFeeDiscount")]
+    fn xoxno_fee_discount(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getFeeTiers)]
+    #[storage_mapper("feeTiers")]
+    fn fee_tiers(&self) -> SingleValueMapper<ManagedVec<FeeTier<Self::Api>>>;
+
+    #[view(getVolumeWindowEpochs)]
+    #[storage_mapper("volumeWindowEpochs")]
+    fn volume_window_epochs(&self) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("userTradeVolume")]
+    fn user_trade_volume(
+        &self,
+        user: &ManagedAddress,
+    ) -> SingleValueMapper<ManagedVec<VolumeBucket<Self::Api>>>;
+}
@@ -0,0 +1,259 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::{
+    auction::{
+        CollectionExtraFeesConfig, CollectionFeeConfig, GovernanceAction, QueuedAction, Role,
+    },
+    creator::MAX_EXTRA_FEE,
+};
+
+// Longest delay the governance timelock can be configured with (30 days)
+const MAX_GOVERNANCE_DELAY: u64 = 2_592_000;
+// Shortest delay the governance timelock can be configured with (1 hour)
+const MIN_GOVERNANCE_DELAY: u64 = 3_600;
+// Delay seeded on deploy and upgrade (1 day)
+pub const DEFAULT_GOVERNANCE_DELAY: u64 = 86_400;
+
+#[multiversx_sc::module]
+pub trait GovernanceModule:
+    crate::storage::StorageModule
+    + crate::helpers::HelpersModule
+    + crate::views::ViewsModule
+    + crate::events::EventsModule
+    + crate::fees::FeesModule
+    + crate::claims::ClaimsModule
+{
+    #[endpoint(setGovernanceDelay)]
+    fn set_governance_delay(&self, delay: u64) {
+        self.require_role(Role::FeeManager, None);
+        self.queue_action(GovernanceAction::SetGovernanceDelay(delay));
+    }
+
+    #[endpoint(setAggregator)]
+    fn set_aggregator(&self, aggregator: ManagedAddress) {
+        self.require_role(Role::FeeManager, None);
+        self.queue_action(GovernanceAction::SetAggregator(aggregator));
+    }
+
+    #[endpoint(setAccumulator)]
+    fn set_accumulator(&self, accumulator: ManagedAddress) {
+        self.require_role(Role::FeeManager, None);
+        self.queue_action(GovernanceAction::SetAccumulator(accumulator));
+    }
+
+    #[endpoint(setWrapping)]
+    fn set_wrapping(&self, wrapping_sc: ManagedAddress, wrapping_token: TokenIdentifier) {
+        self.require_role(Role::FeeManager, None);
+        self.queue_action(GovernanceAction::SetWrapping(wrapping_sc, wrapping_token));
+    }
+
+    // Nominates the claim receiver of a contract that can't call setClaimReceiver itself
+    #[only_owner]
+    #[endpoint(setClaimReceiverFor)]
+    fn set_claim_receiver_for(
+        &self,
+        contract: ManagedAddress,
+        receiver: OptionalValue<ManagedAddress>,
+    ) {
+        self.queue_action(GovernanceAction::SetClaimReceiver(
+            contract,
+            receiver.into_option(),
+        ));
+    }
+
+    // Queues any governed change and returns its id, the setters above queue the same actions
+    #[endpoint(proposeAction)]
+    fn propose_action(&self, action: GovernanceAction<Self::Api>) -> u64 {
+        self.require_action_role(&action);
+        self.queue_action(action)
+    }
+
+    // Anyone can execute an action once its delay elapsed, the checks ran when it was queued
+    #[endpoint(executeAction)]
+    fn execute_action(&self, action_id: u64) {
+        let map_action = self.queued_action(action_id);
+        require!(
+            !map_action.is_empty(),
+            "Action {} is not queued!",
+            action_id
+        );
+        let queued = map_action.take();
+        let current_time = self
+            .blockchain()
+            .get_block_timestamp_seconds()
+            .as_u64_seconds();
+        require!(
+            current_time >= queued.executable_at,
+            "The action is still timelocked!"
+        );
+        self.queued_action_ids().swap_remove(&action_id);
+        self.apply_action(queued.action);
+        self.emit_action_executed_event(action_id);
+    }
+
+    #[only_owner]
+    #[endpoint(cancelAction)]
+    fn cancel_action(&self, action_id: u64) {
+        let map_action = self.queued_action(action_id);
+        require!(
+            !map_action.is_empty(),
+            "Action {} is not queued!",
+            action_id
+        );
+        map_action.clear();
+        self.queued_action_ids().swap_remove(&action_id);
+        self.emit_action_cancelled_event(action_id);
+    }
+
+    fn queue_action(&self, action: GovernanceAction<Self::Api>) -> u64 {
+        self.validate_action(&action);
+        let action_id = self.last_action_id().get() + 1;
+        self.last_action_id().set(action_id);
+        let executable_at = self
+            .blockchain()
+            .get_block_timestamp_seconds()
+            .as_u64_seconds()
+            + self.governance_delay().get();
+        let queued = QueuedAction {
+            action,
+            executable_at,
+        };
+        self.queued_action(action_id).set(&queued);
+        self.queued_action_ids().insert(action_id);
+        self.emit_action_queued_event(action_id, &queued);
+        action_id
+    }
+
+    fn require_action_role(&self, action: &GovernanceAction<Self::Api>) {
+        match action {
+            GovernanceAction::SetCutPercentage(_)
+            | GovernanceAction::SetTokenCutPercentage(_, _)
+            | GovernanceAction::RemoveTokenCutPercentage(_)
+            | GovernanceAction::SetClaimReceiver(_, _) => {
+                require!(
+                    self.blockchain().get_caller() == self.blockchain().get_owner_address(),
+                    "Endpoint can only be called by owner"
+                );
+            }
+            GovernanceAction::SetExtraFees(token_id, _, _) => {
+                let config_map = self.collection_config(token_id);
+                let admin = if config_map.is_empty() {
+                    None
+                } else {
+                    Some(config_map.get().admin)
+                };
+                self.require_role(Role::FeeManager, admin);
+            }
+            GovernanceAction::SetStatus(_) => self.require_role(Role::Pauser, None),
+            _ => self.require_role(Role::FeeManager, None),
+        }
+    }
+
+    fn validate_action(&self, action: &GovernanceAction<Self::Api>) {
+        match action {
+            GovernanceAction::SetCutPercentage(cut_percentage)
+            | GovernanceAction::SetTokenCutPercentage(_, cut_percentage) => {
+                self.require_valid_cut_percentage(*cut_percentage)
+            }
+            GovernanceAction::SetAggregator(address)
+            | GovernanceAction::SetAccumulator(address)
+            | GovernanceAction::SetWrapping(address, _)
+            | GovernanceAction::SetClaimReceiver(address, _) => require!(
+                self.blockchain().is_smart_contract(address),
+                "The address is not a smart contract!"
+            ),
+            GovernanceAction::SetGovernanceDelay(delay) => require!(
+                (MIN_GOVERNANCE_DELAY..=MAX_GOVERNANCE_DELAY).contains(delay),
+                "Governance delay must be between 1 hour and 30 days!"
+            ),
+            GovernanceAction::SetExtraFees(_, amount, _) => {
+                require!(amount <= &MAX_EXTRA_FEE, "Extra fee cannot exceed 20%!")
+            }
+            _ => {}
+        }
+    }
+
+    fn apply_action(&self, action: GovernanceAction<Self::Api>) {
+        match action {
+            GovernanceAction::SetCutPercentage(cut_percentage) => {
+                self.try_set_bid_cut_percentage(cut_percentage)
+            }
+            GovernanceAction::SetTokenCutPercentage(payment_token, cut_percentage) => {
+                self.require_valid_cut_percentage(cut_percentage);
+                let cut_percentage = BigUint::from(cut_percentage);
+                self.token_cut_percentage(&payment_token)
+                    .set(&cut_percentage);
+                self.fee_override_tokens().insert(payment_token.clone());
+                self.emit_token_fee_event(&payment_token, &cut_percentage);
+            }
+            GovernanceAction::RemoveTokenCutPercentage(payment_token) => {
+                self.token_cut_percentage(&payment_token).clear();
+                self.fee_override_tokens().swap_remove(&payment_token);
+                self.emit_token_fee_event(&payment_token, &BigUint::zero());
+            }
+            GovernanceAction::AddAcceptedToken(token) => {
+                self.accepted_tokens().insert(token);
+            }
+            GovernanceAction::RemoveAcceptedToken(token) => {
+                self.accepted_tokens().remove(&token);
+            }
+            GovernanceAction::SetAggregator(aggregator) => self.aggregator_sc().set(aggregator),
+            GovernanceAction::SetAccumulator(accumulator) => self.accumulator().set(accumulator),
+            GovernanceAction::SetWrapping(wrapping_sc, wrapping_token) => {
+                self.wrapping().set(wrapping_sc);
+                self.wrapping_token().set(wrapping_token);
+            }
+            GovernanceAction::SetGovernanceDelay(delay) => self.governance_delay().set(delay),
+            GovernanceAction::SetExtraFees(token_id, amount, address) => {
+                let config_map = self.collection_config(&token_id);
+                if config_map.is_empty() {
+                    config_map.set(CollectionFeeConfig {
+                        reverse_cut_fees: false,
+                        reverse_royalties: false,
+                        custom_royalties: false,
+                        min_royalties: BigUint::zero(),
+                        max_royalties: BigUint::zero(),
+                        extra_fees: CollectionExtraFeesConfig { amount, address },
+                        admin: ManagedAddress::zero(),
+                        royalty_splits: ManagedVec::new(),
+                    });
+                } else {
+                    config_map.update(|f| {
+                        f.extra_fees.amount = amount;
+                        f.extra_fees.address = address;
+                    })
+                }
+                self.emit_collection_config(&token_id, &config_map.get());
+            }
+            GovernanceAction::SetStatus(status) => self.status().set(status),
+            GovernanceAction::SetClaimReceiver(contract, receiver) => {
+                self.apply_claim_receiver(&contract, receiver)
+            }
+        }
+    }
+
+    #[view(getQueuedActions)]
+    fn get_queued_actions(&self) -> MultiValueEncoded<MultiValue2<u64, QueuedAction<Self::Api>>> {
+        let mut results = MultiValueEncoded::new();
+        for action_id in self.queued_action_ids().iter() {
+            results.push((action_id, self.queued_action(action_id).get()).into());
+        }
+        results
+    }
+
+    #[view(getGovernanceDelay)]
+    #[storage_mapper("governanceDelay")]
+    fn governance_delay(&self) -> SingleValueMapper<u64>;
+
+    #[view(getLastActionId)]
+    #[storage_mapper("lastActionId")]
+    fn last_action_id(&self) -> SingleValueMapper<u64>;
+
+    #[view(getQueuedAction)]
+    #[storage_mapper("queuedAction")]
+    fn queued_action(&self, action_id: u64) -> SingleValueMapper<QueuedAction<Self::Api>>;
+
+    #[storage_mapper("queuedActionIds")]
+    fn queued_action_ids(&self) -> UnorderedSetMapper<u64>;
+}
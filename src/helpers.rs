@@ -4,8 +4,11 @@ multiversx_sc::derive_imports!();
 use core::convert::TryInto;
 
 use crate::{
-    auction::{AttributesIns, Auction, CollectionFeeConfig, FeesDistribution, GlobalOffer, Offer},
-    PERCENTAGE_TOTAL,
+    auction::{
+        Auction, CollectionFeeConfig, CreatorSource, Feature, FeesDistribution, GlobalOffer, Offer,
+        Role,
+    },
+    MAX_COLLECTION_ROYALTIES, PERCENTAGE_TOTAL,
 };
 
 // Minimum percentage that must go to the seller (10% = 1000 basis points)
@@ -13,7 +16,10 @@ const MIN_SELLER_PERCENTAGE: u64 = 1_000;
 
 #[multiversx_sc::module]
 pub trait HelpersModule:
-    crate::storage::StorageModule + crate::views::ViewsModule + crate::events::EventsModule
+    crate::storage::StorageModule
+    + crate::views::ViewsModule
+    + crate::events::EventsModule
+    + crate::fees::FeesModule
 {
     fn transfer_or_save_payment(
         &self,
@@ -42,9 +48,18 @@ pub trait HelpersModule:
             nft_nonce,
         );
 
-        if nft_type == &TokenIdentifier::from_esdt_bytes(b"INS-dd5a76") {
-            let attributes = data.decode_attributes::<AttributesIns<Self::Api>>();
-            data.creator = attributes.creator;
+        let map_source = self.creator_source(nft_type);
+        if !map_source.is_empty() {
+            match map_source.get() {
+                CreatorSource::Attributes { offset } => {
+                    if let Some(creator) = data.attributes.copy_slice(offset, 32) {
+                        if let Ok(creator) = ManagedAddress::try_from(creator) {
+                            data.creator = creator;
+                        }
+                    }
+                }
+                CreatorSource::Fixed(creator) => data.creator = creator,
+            }
         }
 
         data
@@ -69,15 +84,19 @@ pub trait HelpersModule:
     }
 
     fn try_set_bid_cut_percentage(&self, new_cut_percentage: u64) {
-        require!(
-            new_cut_percentage > 0 && new_cut_percentage < PERCENTAGE_TOTAL,
-            "Invalid percentage value, should be between 0 and 10,000"
-        );
+        self.require_valid_cut_percentage(new_cut_percentage);
 
         self.bid_cut_percentage()
             .set(BigUint::from(new_cut_percentage));
     }
 
+    fn require_valid_cut_percentage(&self, cut_percentage: u64) {
+        require!(
+            cut_percentage > 0 && cut_percentage < PERCENTAGE_TOTAL,
+            "Invalid percentage value, should be between 0 and 10,000"
+        );
+    }
+
     fn calculate_cut_amount(&self, total_amount: &BigUint, cut_percentage: &BigUint) -> BigUint {
         total_amount * cut_percentage / PERCENTAGE_TOTAL
     }
@@ -97,21 +116,43 @@ pub trait HelpersModule:
         &self,
         price: &BigUint,
         royalties: &BigUint,
+        cut_percentage: &BigUint,
         config: Option<CollectionFeeConfig<Self::Api>>,
+        seller: &ManagedAddress,
     ) -> FeesDistribution<Self::Api> {
-        let fees = self.bid_cut_percentage().get();
+        let split =
+            self.try_calculate_amount_split(price, royalties, cut_percentage, config, seller);
+        require!(
+            split.is_some(),
+            "Combined fees cannot exceed 90%! Seller must receive at least 10%."
+        );
+        split.unwrap()
+    }
+
+    // Returns None instead of failing when the combined fees leave the seller under 10%
+    fn try_calculate_amount_split(
+        &self,
+        price: &BigUint,
+        royalties: &BigUint,
+        cut_percentage: &BigUint,
+        config: Option<CollectionFeeConfig<Self::Api>>,
+        seller: &ManagedAddress,
+    ) -> Option<FeesDistribution<Self::Api>> {
+        let fees = self.apply_fee_discounts(seller, cut_percentage.clone());
         let mut eligible_royalties = royalties.clone();
         let mut extra_amount = BigUint::zero();
         let mut reverse_royalties = false;
         let mut reverse_cut_fees = false;
         let mut extra_fee = BigUint::zero();
         let mut extra_address = ManagedAddress::zero();
+        let mut royalty_splits = ManagedVec::new();
 
         if let Some(config) = config {
             extra_fee = config.extra_fees.amount;
             extra_address = config.extra_fees.address;
             reverse_royalties = config.reverse_royalties;
             reverse_cut_fees = config.reverse_cut_fees;
+            royalty_splits = config.royalty_splits;
             if config.custom_royalties {
                 if config.max_royalties < eligible_royalties {
                     eligible_royalties = config.max_royalties;
@@ -122,10 +163,9 @@ pub trait HelpersModule:
         }
 
         // Ensure combined fees don't exceed 90%, leaving at least 10% for the seller
-        require!(
-            &fees + &eligible_royalties + &extra_fee <= PERCENTAGE_TOTAL - MIN_SELLER_PERCENTAGE,
-            "Combined fees cannot exceed 90%! Seller must receive at least 10%."
-        );
+        if &fees + &eligible_royalties + &extra_fee > PERCENTAGE_TOTAL - MIN_SELLER_PERCENTAGE {
+            return None;
+        }
         let creator_royalties = self.calculate_cut_amount(price, &eligible_royalties);
         let marketplace_fees = self.calculate_cut_amount(price, &fees);
         let mut seller_amount_to_send = price.clone();
@@ -136,7 +176,7 @@ pub trait HelpersModule:
             seller_amount_to_send -= &extra_amount;
         }
 
-        FeesDistribution {
+        Some(FeesDistribution {
             creator: creator_royalties,
             marketplace: marketplace_fees,
             extra: extra_amount,
@@ -144,7 +184,66 @@ pub trait HelpersModule:
             extra_address,
             reverse_royalties,
             reverse_cut_fees,
+            referral: BigUint::zero(),
+            referrer: ManagedAddress::zero(),
+            royalty_splits,
+        })
+    }
+
+    // Bounds the royalties by the collection config, the absolute cap and the fees budget
+    fn cap_royalties(
+        &self,
+        collection: &TokenIdentifier,
+        royalties: BigUint,
+        cut_percentage: &BigUint,
+    ) -> BigUint {
+        let mut capped = royalties;
+        let mut extra_fee = BigUint::zero();
+        if let Some(config) = self.get_collection_config(collection) {
+            if config.custom_royalties {
+                if config.max_royalties < capped {
+                    capped = config.max_royalties;
+                } else if config.min_royalties > capped {
+                    capped = config.min_royalties;
+                }
+            }
+            extra_fee = config.extra_fees.amount;
+        }
+        if capped > MAX_COLLECTION_ROYALTIES {
+            capped = BigUint::from(MAX_COLLECTION_ROYALTIES);
+        }
+        let budget = BigUint::from(PERCENTAGE_TOTAL - MIN_SELLER_PERCENTAGE);
+        let other_fees = cut_percentage + &extra_fee;
+        if &other_fees + &capped > budget {
+            capped = if other_fees < budget {
+                budget - other_fees
+            } else {
+                BigUint::zero()
+            };
         }
+        capped
+    }
+
+    // Leaves are sha256(address), pairs are hashed in sorted order
+    fn verify_merkle_proof(
+        &self,
+        root: &ManagedByteArray<32>,
+        address: &ManagedAddress,
+        proof: &ManagedVec<ManagedByteArray<32>>,
+    ) -> bool {
+        let mut hash = self.crypto().sha256(address.as_managed_buffer());
+        for sibling in proof.iter() {
+            let mut data = ManagedBuffer::new();
+            if hash.to_byte_array() <= sibling.to_byte_array() {
+                data.append(hash.as_managed_buffer());
+                data.append(sibling.as_managed_buffer());
+            } else {
+                data.append(sibling.as_managed_buffer());
+                data.append(hash.as_managed_buffer());
+            }
+            hash = self.crypto().sha256(&data);
+        }
+        &hash == root
     }
 
     fn decimal_to_ascii(&self, mut number: u32) -> ManagedBuffer {
@@ -172,24 +271,47 @@ pub trait HelpersModule:
         ManagedBuffer::new_from_bytes(slice)
     }
 
-    fn require_admin(&self, extra_admin: Option<ManagedAddress>) {
-        let signer: ManagedAddress = self.signer().get();
+    // The owner always passes, the extra admin is the collection config admin where relevant
+    fn require_role(&self, role: Role, extra_admin: Option<ManagedAddress>) {
         let caller = self.blockchain().get_caller();
         let sc_owner = self.blockchain().get_owner_address();
-        if extra_admin.is_some() {
-            require!(
-                caller.eq(&sc_owner) || caller.eq(&signer) || caller.eq(&extra_admin.unwrap()),
-                "You are not an admin!"
-            );
-        } else {
+        require!(
+            caller.eq(&sc_owner)
+                || self.role_members(role).contains(&caller)
+                || extra_admin.is_some_and(|admin| caller.eq(&admin)),
+            "You are not an admin!"
+        );
+    }
+
+    fn require_enabled(&self) {
+        require!(self.status().get(), "Global operation enabled!");
+    }
+
+    fn require_feature_enabled(&self, feature: Feature) {
+        self.require_enabled();
+        require!(
+            !self.feature_paused(feature).get(),
+            "This feature is currently paused!"
+        );
+    }
+
+    fn require_collection_allowed(&self, collection: &TokenIdentifier) {
+        require!(
+            !self.denied_collections().contains(collection),
+            "This collection is denylisted!"
+        );
+        if self.curated_mode().get() {
             require!(
-                caller.eq(&sc_owner) || caller.eq(&signer),
-                "You are not an admin!"
+                self.allowed_collections().contains(collection),
+                "This collection is not allowlisted!"
             );
         }
     }
 
-    fn require_enabled(&self) {
-        require!(self.status().get(), "Global operation enabled!");
+    fn require_collection_active(&self, collection: &TokenIdentifier) {
+        require!(
+            !self.halted_collections().contains(collection),
+            "Trading is halted for this collection!"
+        );
     }
 }
@@ -4,14 +4,25 @@ multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 pub mod auction;
 use auction::*;
+use governance::DEFAULT_GOVERNANCE_DELAY;
 pub mod accumulator;
 pub mod admin;
+pub mod claims;
 pub mod common;
 pub mod creator;
 pub mod events;
+pub mod fees;
+pub mod governance;
 pub mod helpers;
+pub mod liquid;
+pub mod nft_swaps;
 pub mod offers;
+pub mod orders;
 pub mod pools;
+pub mod raffles;
+pub mod referrals;
+pub mod rentals;
+pub mod staking;
 pub mod storage;
 pub mod views;
 pub mod wrapping;
@@ -21,6 +32,7 @@ const MAX_COLLECTION_ROYALTIES: u64 = 5_000; // 50%
 const NFT_AMOUNT: u32 = 1; // Token has to be unique to be considered NFT
 const MIN_TRADE_REWARD: u64 = 200_000_000_000_000_000; // Minimum trade value for rewards (0.2 EGLD)
 const MAX_BULK_ITEMS: usize = 80; // Maximum items in bulk operations to prevent gas griefing
+const MAX_BUNDLE_ITEMS: usize = 20; // Maximum tokens escrowed under one bundle listing
 
 #[multiversx_sc::contract]
 pub trait XOXNOProtocol:
@@ -34,6 +46,15 @@ pub trait XOXNOProtocol:
     + wrapping::WrappingModule
     + common::CommonModule
     + pools::PoolsModule
+    + staking::StakingModule
+    + claims::ClaimsModule
+    + fees::FeesModule
+    + referrals::ReferralsModule
+    + governance::GovernanceModule
+    + orders::OrdersModule
+    + nft_swaps::NftSwapsModule
+    + rentals::RentalsModule
+    + raffles::RafflesModule
 {
     #[init]
     fn init(
@@ -47,9 +68,12 @@ pub trait XOXNOProtocol:
     ) {
         self.try_set_bid_cut_percentage(bid_cut_percentage);
         self.signer().set_if_empty(&signer);
+        // The backend signing key gets the freeze rights it needs for swaps and nothing else
+        self.role_members(Role::Signer).insert(self.signer().get());
         self.wrapping().set(wrapping_sc);
         self.wrapping_token().set(wrapping_token);
         self.aggregator_sc().set(aggregator);
+        self.governance_delay().set(DEFAULT_GOVERNANCE_DELAY);
         // self.xoxno_token().set(xoxno_token);
     }
 
@@ -57,14 +81,19 @@ pub trait XOXNOProtocol:
     fn upgrade(&self, sc_accumulator: ManagedAddress, aggregator: ManagedAddress) {
         self.accumulator().set(sc_accumulator);
         self.aggregator_sc().set(aggregator);
+        self.creator_source(&TokenIdentifier::from_esdt_bytes(b"INS-dd5a76"))
+            .set_if_empty(CreatorSource::Attributes { offset: 0 });
+        // The backend signing key keeps the freeze rights it needs for swaps and nothing else
+        self.role_members(Role::Signer).insert(self.signer().get());
+        self.governance_delay()
+            .set_if_empty(DEFAULT_GOVERNANCE_DELAY);
     }
 
     #[payable("*")]
     #[endpoint(listing)]
     fn listing(&self, listings: MultiValueEncoded<BulkListing<Self::Api>>) {
-        self.require_enabled();
+        self.require_feature_enabled(Feature::Listing);
         let payments = self.call_value().all_esdt_transfers();
-        let marketplace_cut_percentage = &self.bid_cut_percentage().get();
         let current_time = self.blockchain().get_block_timestamp_seconds().as_u64_seconds();
         let caller = self.blockchain().get_caller();
 
@@ -76,6 +105,8 @@ pub trait XOXNOProtocol:
         require!(listings.len() == payments.len(), "Invalid body sent!");
         for (index, listing) in listings.to_vec().iter().enumerate() {
             let (nft_type, nft_nonce, nft_amount) = payments.get(index).clone().into_tuple();
+            self.require_collection_active(&nft_type);
+            self.require_collection_allowed(&nft_type);
             require!(
                 map_acc_tokens.contains(&listing.accepted_payment_token),
                 "The payment token is not whitelisted!"
@@ -134,6 +165,8 @@ pub trait XOXNOProtocol:
                     "Invalid start time"
                 );
             }
+            let marketplace_cut_percentage =
+                &self.get_marketplace_cut(&listing.accepted_payment_token);
             let fee_map = self.collection_config(&nft_type);
             let mut creator_royalties_percentage =
                 self.get_nft_info(&nft_type, nft_nonce).royalties;
@@ -185,6 +218,13 @@ pub trait XOXNOProtocol:
                     "Deadline is mandatory for this auction type!"
                 );
             }
+            if listing.merkle_root.is_some() {
+                require!(
+                    auction_type == AuctionType::Nft
+                        || auction_type == AuctionType::SftOnePerPayment,
+                    "Only fixed price listings can be allowlist gated!"
+                );
+            }
 
             let auction = Auction {
                 auctioned_token_type: nft_type.clone(),
@@ -205,6 +245,9 @@ pub trait XOXNOProtocol:
                 current_winner: ManagedAddress::zero(),
                 marketplace_cut_percentage: marketplace_cut_percentage.clone(),
                 creator_royalties_percentage,
+                reserved_buyer: listing.reserved_buyer.clone(),
+                merkle_root: listing.merkle_root.clone(),
+                bundle_items: ManagedVec::new(),
             };
 
             // Map ID with Auction Struct
@@ -228,10 +271,137 @@ pub trait XOXNOProtocol:
         }
     }
 
+    #[payable("*")]
+    #[endpoint(listBundle)]
+    fn list_bundle(&self, listing: BundleListing<Self::Api>) -> u64 {
+        self.require_feature_enabled(Feature::Listing);
+        let payments = self.call_value().all_esdt_transfers().clone_value();
+        let current_time = self.blockchain().get_block_timestamp_seconds().as_u64_seconds();
+        let caller = self.blockchain().get_caller();
+
+        require!(
+            payments.len() > 1 && payments.len() <= MAX_BUNDLE_ITEMS,
+            "A bundle must contain between 2 and 20 tokens!"
+        );
+        require!(
+            self.accepted_tokens()
+                .contains(&listing.accepted_payment_token),
+            "The payment token is not whitelisted!"
+        );
+        require!(listing.min_bid > 0u32, "Min bid must be higher than 0!");
+        if !listing.bid {
+            require!(
+                listing.min_bid == listing.max_bid,
+                "Price must be fixed for this type of auction (min bid equal to max bid)"
+            );
+        } else {
+            require!(
+                listing.deadline != 0,
+                "Deadline is mandatory for this auction type!"
+            );
+        }
+        let opt_max_bid = if listing.max_bid > 0u32 {
+            require!(
+                listing.min_bid <= listing.max_bid,
+                "Min bid can't higher than max bid"
+            );
+            Some(listing.max_bid.clone())
+        } else {
+            None
+        };
+        require!(
+            listing.deadline > current_time || listing.deadline == 0,
+            "Deadline can't be in the past"
+        );
+        let start_time = if listing.opt_start_time == 0 {
+            current_time
+        } else {
+            listing.opt_start_time
+        };
+        if listing.deadline != 0 {
+            require!(
+                start_time >= current_time && start_time < listing.deadline,
+                "Invalid start time"
+            );
+        }
+
+        // The bundle pays the average royalty of its items, weighted by their amounts
+        let marketplace_cut_percentage = self.get_marketplace_cut(&listing.accepted_payment_token);
+        let mut highest_royalties = BigUint::zero();
+        let mut weighted_royalties = BigUint::zero();
+        let mut total_amount = BigUint::zero();
+        let mut primary_index = 0;
+        for (index, item) in payments.iter().enumerate() {
+            require!(
+                item.token_nonce > 0,
+                "Only Semi-Fungible and Non-Fungible tokens can be auctioned"
+            );
+            self.require_collection_active(&item.token_identifier);
+            self.require_collection_allowed(&item.token_identifier);
+            let royalties = self.bundle_item_royalties(&item, &marketplace_cut_percentage);
+            weighted_royalties += &royalties * &item.amount;
+            total_amount += &item.amount;
+            if royalties > highest_royalties {
+                highest_royalties = royalties;
+                primary_index = index;
+            }
+        }
+        let creator_royalties_percentage = weighted_royalties / total_amount;
+
+        let auction_id = self.last_valid_auction_id().get() + 1;
+        self.last_valid_auction_id().set(auction_id);
+
+        // The bundle is indexed and sold as a single unit of its highest royalty item, whose fees
+        // budget then bounds the average royalty charged on sale
+        let primary = payments.get(primary_index).clone();
+        let mut bundle_items = ManagedVec::from_single_item(primary.clone());
+        for (index, item) in payments.iter().enumerate() {
+            if index != primary_index {
+                bundle_items.push(item.clone());
+            }
+        }
+        let auction = Auction {
+            auctioned_token_type: primary.token_identifier.clone(),
+            auctioned_token_nonce: primary.token_nonce,
+            nr_auctioned_tokens: BigUint::from(NFT_AMOUNT),
+            auction_type: match listing.bid {
+                true => AuctionType::NftBid,
+                false => AuctionType::Nft,
+            },
+            payment_token_type: listing.accepted_payment_token,
+            payment_token_nonce: 0,
+            min_bid: listing.min_bid,
+            max_bid: opt_max_bid,
+            start_time,
+            deadline: listing.deadline,
+            original_owner: caller.clone(),
+            current_bid: BigUint::zero(),
+            current_winner: ManagedAddress::zero(),
+            marketplace_cut_percentage,
+            creator_royalties_percentage,
+            reserved_buyer: listing.reserved_buyer,
+            merkle_root: None,
+            bundle_items,
+        };
+
+        self.auction_by_id(auction_id).set(&auction);
+        self.listings().insert(auction_id);
+        self.listings_by_wallet(&caller).insert(auction_id);
+        self.token_items_for_sale(&primary.token_identifier)
+            .insert(primary.token_nonce);
+        self.token_auction_ids(&primary.token_identifier, primary.token_nonce)
+            .insert(auction_id);
+        self.token_items_quantity_for_sale(&primary.token_identifier, primary.token_nonce)
+            .update(|qt| *qt += NFT_AMOUNT);
+        self.collections_listed().insert(primary.token_identifier);
+        self.emit_auction_token_event(auction_id, auction);
+        auction_id
+    }
+
     #[payable("*")]
     #[endpoint(bid)]
     fn bid(&self, auction_id: u64, nft_type: TokenIdentifier, nft_nonce: u64) {
-        self.require_enabled();
+        self.require_feature_enabled(Feature::Bidding);
         let (payment_token, payment_token_nonce, payment_amount) =
             self.call_value().egld_or_single_esdt().into_tuple();
         // NOTE: Freeze check removed here as it's already performed in common_bid_checks
@@ -255,6 +425,12 @@ pub trait XOXNOProtocol:
                 || auction.auction_type == AuctionType::NftBid,
             "Cannot bid on this type of auction!"
         );
+        if let Some(reserved_buyer) = &auction.reserved_buyer {
+            require!(
+                reserved_buyer == &caller,
+                "This listing is reserved for another buyer!"
+            );
+        }
 
         let mut max_bid_reached = false;
         if let Some(max_bid) = &auction.max_bid {
@@ -348,6 +524,7 @@ pub trait XOXNOProtocol:
         self.end_auction_common(auction_id, &auction);
     }
 
+    #[allow_multiple_var_args]
     #[payable("*")]
     #[endpoint(buy)]
     fn buy(
@@ -356,6 +533,8 @@ pub trait XOXNOProtocol:
         nft_type: TokenIdentifier,
         nft_nonce: u64,
         opt_sft_buy_amount: OptionalValue<BigUint>,
+        referrer: OptionalValue<ManagedAddress>,
+        proof: OptionalValue<ManagedVec<ManagedByteArray<32>>>,
     ) {
         self.common_buy(
             auction_id,
@@ -365,6 +544,8 @@ pub trait XOXNOProtocol:
             OptionalValue::None,
             OptionalValue::None,
             OptionalValue::None,
+            referrer.into_option(),
+            proof.into_option(),
         );
     }
 
@@ -387,6 +568,8 @@ pub trait XOXNOProtocol:
             OptionalValue::None,
             OptionalValue::None,
             OptionalValue::Some(steps),
+            None,
+            None,
         );
     }
 
@@ -410,6 +593,8 @@ pub trait XOXNOProtocol:
             buy_for,
             message,
             OptionalValue::None,
+            None,
+            None,
         );
     }
 
@@ -419,6 +604,25 @@ pub trait XOXNOProtocol:
         &self,
         auction_ids: MultiValueEncoded<u64>,
     ) -> ManagedVec<EsdtTokenPayment<Self::Api>> {
+        self.bulk_buy_common(auction_ids, None)
+    }
+
+    #[payable("*")]
+    #[endpoint(bulkBuyReferred)]
+    fn bulk_buy_referred(
+        &self,
+        referrer: ManagedAddress,
+        auction_ids: MultiValueEncoded<u64>,
+    ) -> ManagedVec<EsdtTokenPayment<Self::Api>> {
+        self.bulk_buy_common(auction_ids, Some(referrer))
+    }
+
+    fn bulk_buy_common(
+        &self,
+        auction_ids: MultiValueEncoded<u64>,
+        referrer: Option<ManagedAddress>,
+    ) -> ManagedVec<EsdtTokenPayment<Self::Api>> {
+        self.require_feature_enabled(Feature::Buying);
         // SECURITY FIX: Limit bulk operations to prevent gas griefing
         require!(
             auction_ids.len() <= MAX_BULK_ITEMS,
@@ -432,6 +636,9 @@ pub trait XOXNOProtocol:
         let caller = self.blockchain().get_caller();
         let wegld = self.wrapping_token().get();
         let mut marketplace_fees = BigUint::zero();
+        let mut xoxno_fees = BigUint::zero();
+        let mut referral_fees = BigUint::zero();
+        let mut referral_trades = 0u64;
 
         let map_frozen = self.freezed_auctions();
 
@@ -446,6 +653,20 @@ pub trait XOXNOProtocol:
                 listing.auction_type == AuctionType::Nft,
                 "You can bulk buy just NFTs on sell with a fixed price!"
             );
+            if let Some(reserved_buyer) = &listing.reserved_buyer {
+                require!(
+                    reserved_buyer == &caller,
+                    "This listing is reserved for another buyer!"
+                );
+            }
+            require!(
+                listing.merkle_root.is_none(),
+                "Allowlist gated listings can't be bulk bought!"
+            );
+            require!(
+                listing.bundle_items.is_empty(),
+                "Bundle listings can't be bulk bought!"
+            );
 
             require!(
                 total_available >= listing.min_bid,
@@ -473,20 +694,36 @@ pub trait XOXNOProtocol:
             listing.current_winner = caller.clone();
             let config = self.get_collection_config(&listing.auctioned_token_type);
 
-            let bid_split_amounts = self.calculate_amount_split(
+            let mut bid_split_amounts = self.calculate_amount_split(
                 &listing.current_bid,
                 &listing.creator_royalties_percentage,
+                &listing.marketplace_cut_percentage,
                 config.clone(),
+                &listing.original_owner,
+            );
+            self.apply_referral(
+                &mut bid_split_amounts,
+                referrer.as_ref(),
+                &caller,
+                &listing.original_owner,
             );
 
-            if config.is_some() {
-                if config.unwrap().reverse_cut_fees {
-                    total_available += &bid_split_amounts.marketplace;
-                } else {
-                    marketplace_fees += &bid_split_amounts.marketplace;
-                }
+            let reverse_cut_fees = config.is_some() && config.unwrap().reverse_cut_fees;
+            if reverse_cut_fees {
+                total_available += &bid_split_amounts.marketplace;
+            } else if let Some(xoxno_fee) = self.take_xoxno_fee(
+                &caller,
+                &listing.payment_token_type,
+                &(&bid_split_amounts.marketplace - &bid_split_amounts.referral),
+            ) {
+                total_available += &bid_split_amounts.marketplace - &bid_split_amounts.referral;
+                xoxno_fees += xoxno_fee;
             } else {
-                marketplace_fees += &bid_split_amounts.marketplace;
+                marketplace_fees += &bid_split_amounts.marketplace - &bid_split_amounts.referral;
+            }
+            if bid_split_amounts.referral > 0 {
+                referral_fees += &bid_split_amounts.referral;
+                referral_trades += 1;
             }
 
             self.distribute_tokens_bulk_buy(
@@ -538,6 +775,24 @@ pub trait XOXNOProtocol:
                 payments.token_nonce,
             );
         }
+
+        if let Some(referrer) = &referrer {
+            self.share_referral_fees(
+                referrer,
+                &payments.token_identifier,
+                &referral_fees,
+                payments.token_nonce,
+                referral_trades,
+            );
+        }
+
+        if xoxno_fees > BigUint::zero() {
+            self.share_marketplace_fees(
+                &EgldOrEsdtTokenIdentifier::esdt(self.xoxno_token().get()),
+                xoxno_fees,
+                0,
+            );
+        }
         bought_nfts
     }
 
@@ -581,9 +836,44 @@ pub trait XOXNOProtocol:
         }
     }
 
+    // Returns the auction id to resume from, 0 once every withdrawable listing was processed
+    #[endpoint(withdrawAllListings)]
+    fn withdraw_all_listings(&self, from: OptionalValue<u64>) -> u64 {
+        self.require_enabled();
+        let caller = self.blockchain().get_caller();
+        let map_frozen = self.freezed_auctions();
+        let map_wallet_listings = self.listings_by_wallet(&caller);
+        let from = from.into_option().unwrap_or_default();
+        let iter = if from == 0 || !map_wallet_listings.contains(&from) {
+            map_wallet_listings.iter()
+        } else {
+            map_wallet_listings.iter_from(&from)
+        };
+        let auction_ids: ManagedVec<u64> = iter.take(MAX_BULK_ITEMS + 1).collect();
+
+        for (index, auction_id) in auction_ids.iter().enumerate() {
+            if index == MAX_BULK_ITEMS {
+                return auction_id;
+            }
+            if map_frozen.contains(&auction_id) {
+                continue;
+            }
+            let listing = self.try_get_auction(auction_id);
+            // Auctions with a winning bid can only be ended, not withdrawn
+            if !listing.current_winner.is_zero()
+                && listing.auction_type != AuctionType::SftOnePerPayment
+                && listing.auction_type != AuctionType::Nft
+            {
+                continue;
+            }
+            self.withdraw_auction_common(auction_id, &listing);
+        }
+        0
+    }
+
     #[endpoint(changeListing)]
     fn bulk_change_listing(&self, updates: MultiValueEncoded<BulkUpdateListing<Self::Api>>) {
-        self.require_enabled();
+        self.require_feature_enabled(Feature::Listing);
         let caller = self.blockchain().get_caller();
         require!(!updates.is_empty(), "You can not send len 0 of updates!");
         let map_frozen = self.freezed_auctions();
@@ -608,6 +898,10 @@ pub trait XOXNOProtocol:
                 listing.original_owner == caller,
                 "Only the original owner can change the listing info!"
             );
+            if listing.payment_token_type != update.payment_token_type {
+                listing.marketplace_cut_percentage =
+                    self.get_marketplace_cut(&update.payment_token_type);
+            }
             listing.payment_token_type = update.payment_token_type;
             listing.deadline = update.deadline;
             self.emit_change_listing_event(update.auction_id, &listing, &update.new_price);
@@ -0,0 +1,359 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::auction::{Feature, NftSwap, Role, SwapRequest};
+
+// Maximum number of NFTs on each side of a swap
+const MAX_SWAP_ITEMS: usize = 20;
+
+#[multiversx_sc::module]
+pub trait NftSwapsModule:
+    crate::storage::StorageModule
+    + crate::helpers::HelpersModule
+    + crate::views::ViewsModule
+    + crate::events::EventsModule
+    + crate::common::CommonModule
+    + crate::wrapping::WrappingModule
+    + crate::pools::PoolsModule
+    + crate::staking::StakingModule
+    + crate::fees::FeesModule
+    + crate::referrals::ReferralsModule
+{
+    #[payable("*")]
+    #[endpoint(createSwap)]
+    fn create_swap(
+        &self,
+        deadline: u64,
+        requested: MultiValueEncoded<SwapRequest<Self::Api>>,
+    ) -> u64 {
+        self.require_feature_enabled(Feature::Swaps);
+        let caller = self.blockchain().get_caller();
+        let current_time = self
+            .blockchain()
+            .get_block_timestamp_seconds()
+            .as_u64_seconds();
+        require!(
+            !self.is_blacklisted(&caller),
+            "Your address was blacklisted!"
+        );
+        require!(
+            deadline == 0 || deadline > current_time,
+            "Deadline can't be in the past!"
+        );
+
+        let mut offered = ManagedVec::new();
+        let mut sweetener: Option<EgldOrEsdtTokenPayment> = None;
+        for payment in self.call_value().all_transfers().iter() {
+            if payment.token_nonce > 0 {
+                let collection = payment.token_identifier.clone().unwrap_esdt();
+                self.require_collection_active(&collection);
+                self.require_collection_allowed(&collection);
+                offered.push(payment.clone().unwrap_esdt());
+            } else {
+                require!(
+                    sweetener.is_none(),
+                    "Only one fungible payment can be added!"
+                );
+                require!(
+                    self.accepted_tokens().contains(&payment.token_identifier),
+                    "The payment token is not whitelisted!"
+                );
+                sweetener = Some(payment.clone());
+            }
+        }
+
+        let requested = requested.to_vec();
+        require!(!offered.is_empty(), "You need to offer at least one NFT!");
+        require!(
+            !requested.is_empty(),
+            "You need to request at least one NFT!"
+        );
+        require!(
+            offered.len() <= MAX_SWAP_ITEMS && requested.len() <= MAX_SWAP_ITEMS,
+            "Cannot swap more than 20 NFTs on each side!"
+        );
+        let mut royalty_reserve = BigUint::zero();
+        for request in requested.iter() {
+            require!(
+                request.amount > 0,
+                "Requested amount must be higher than 0!"
+            );
+            self.require_collection_active(&request.collection);
+            self.require_collection_allowed(&request.collection);
+            royalty_reserve += self.swap_royalty(&request.collection).get() * &request.amount;
+        }
+        // Set aside now so a taker's fill can't fail on the maker's balance
+        if royalty_reserve > 0 {
+            self.has_free_balance_and_deduct(
+                &caller,
+                &EgldOrEsdtTokenIdentifier::egld(),
+                0,
+                &royalty_reserve,
+            );
+        }
+
+        let swap_id = self.last_swap_id().get() + 1;
+        self.last_swap_id().set(swap_id);
+        let swap = NftSwap {
+            maker: caller.clone(),
+            offered,
+            sweetener,
+            requested,
+            royalty_reserve,
+            deadline,
+            timestamp: current_time,
+        };
+        self.swap_by_id(swap_id).set(&swap);
+        self.swaps_by_wallet(&caller).insert(swap_id);
+        self.emit_swap_create_event(swap_id, &swap);
+        swap_id
+    }
+
+    #[payable("*")]
+    #[endpoint(fillSwap)]
+    fn fill_swap(&self, swap_id: u64) {
+        self.require_feature_enabled(Feature::Swaps);
+        let swap = self.try_get_swap(swap_id);
+        let taker = self.blockchain().get_caller();
+        let current_time = self
+            .blockchain()
+            .get_block_timestamp_seconds()
+            .as_u64_seconds();
+        require!(swap.maker != taker, "Cannot fill your own swap!");
+        require!(
+            !self.is_blacklisted(&taker),
+            "Your address was blacklisted!"
+        );
+        require!(
+            !self.is_blacklisted(&swap.maker),
+            "Swap owner has been blacklisted!"
+        );
+        require!(
+            swap.deadline == 0 || current_time < swap.deadline,
+            "The swap has expired!"
+        );
+
+        let received = self.call_value().all_esdt_transfers().clone_value();
+        require!(
+            received.len() == swap.requested.len(),
+            "The tokens sent are not matching the swap!"
+        );
+        let mut matched = ManagedVec::<Self::Api, bool>::new();
+        for _ in 0..received.len() {
+            matched.push(false);
+        }
+        for request in swap.requested.iter() {
+            let mut found = false;
+            for (index, nft) in received.iter().enumerate() {
+                if !matched.get(index)
+                    && nft.token_identifier == request.collection
+                    && (request.nonce == 0 || nft.token_nonce == request.nonce)
+                    && nft.amount == request.amount
+                {
+                    let _ = matched.set(index, true);
+                    found = true;
+                    break;
+                }
+            }
+            require!(found, "The tokens sent are not matching the swap!");
+        }
+
+        self.remove_swap(swap_id, &swap);
+        self.emit_swap_fill_event(swap_id, &swap, &taker, &received);
+        // Each side owes the swap royalties of the NFTs it receives, on top of the percentage
+        // royalties a sweetener pays for the taker's NFTs
+        if let Some(sweetener) = &swap.sweetener {
+            self.settle_sweetener(&swap.maker, &taker, sweetener, &received);
+        }
+        self.settle_royalty_reserve(&swap.maker, &swap.royalty_reserve, &received);
+        self.charge_swap_royalties(&taker, &swap.offered);
+        self.tx().to(&swap.maker).multi_esdt(received).transfer();
+        self.tx().to(&taker).multi_esdt(swap.offered).transfer();
+    }
+
+    // Flat EGLD royalty charged per NFT of the collection that changes hands in a swap
+    #[endpoint(setSwapRoyalty)]
+    fn set_swap_royalty(&self, collection: TokenIdentifier, amount: BigUint) {
+        let admin = self
+            .get_collection_config(&collection)
+            .map(|config| config.admin);
+        self.require_role(Role::FeeManager, admin);
+        self.swap_royalty(&collection).set(&amount);
+        self.emit_swap_royalty_event(&collection, &amount);
+    }
+
+    #[endpoint(cancelSwap)]
+    fn cancel_swap(&self, swap_id: u64) {
+        let swap = self.try_get_swap(swap_id);
+        let caller = self.blockchain().get_caller();
+        require!(swap.maker == caller, "Only the maker can cancel the swap!");
+        self.remove_swap(swap_id, &swap);
+        self.emit_swap_cancel_event(swap_id, &swap);
+        if let Some(sweetener) = &swap.sweetener {
+            self.tx().to(&caller).payment(sweetener).transfer();
+        }
+        if swap.royalty_reserve > 0 {
+            self.credit_balance(
+                &caller,
+                EgldOrEsdtTokenPayment::new(
+                    EgldOrEsdtTokenIdentifier::egld(),
+                    0,
+                    swap.royalty_reserve.clone(),
+                ),
+            );
+        }
+        self.tx().to(&caller).multi_esdt(swap.offered).transfer();
+    }
+
+    // The sweetener pays for the taker's NFTs, split evenly and charged with each item's royalties
+    fn settle_sweetener(
+        &self,
+        maker: &ManagedAddress,
+        taker: &ManagedAddress,
+        sweetener: &EgldOrEsdtTokenPayment,
+        nfts: &ManagedVec<EsdtTokenPayment>,
+    ) {
+        let token = &sweetener.token_identifier;
+        let nonce = sweetener.token_nonce;
+        let cut_percentage = self.get_marketplace_cut(token);
+        let item_count = nfts.len() as u64;
+        let item_price = &sweetener.amount / item_count;
+        let mut remaining = sweetener.amount.clone();
+        let mut marketplace = BigUint::zero();
+        let mut maker_refund = BigUint::zero();
+        let mut seller = BigUint::zero();
+        for (index, nft) in nfts.iter().enumerate() {
+            // The last item takes the division dust
+            let price = if index + 1 == nfts.len() {
+                remaining.clone()
+            } else {
+                item_price.clone()
+            };
+            remaining -= &price;
+            let nft_info = self.get_nft_info(&nft.token_identifier, nft.token_nonce);
+            let royalties =
+                self.cap_royalties(&nft.token_identifier, nft_info.royalties, &cut_percentage);
+            let split = self.calculate_amount_split(
+                &price,
+                &royalties,
+                &cut_percentage,
+                self.get_collection_config(&nft.token_identifier),
+                taker,
+            );
+
+            if split.extra > BigUint::zero() && !split.extra_address.is_zero() {
+                self.transfer_or_save_payment(&split.extra_address, token, nonce, &split.extra);
+            }
+            if split.reverse_cut_fees {
+                maker_refund += &split.marketplace;
+            } else {
+                marketplace += &split.marketplace;
+            }
+            if split.reverse_royalties {
+                maker_refund += &split.creator;
+            } else {
+                self.share_royalties(
+                    &nft_info.creator,
+                    &split.royalty_splits,
+                    token,
+                    split.creator.clone(),
+                    nonce,
+                );
+            }
+            seller += &split.seller;
+        }
+
+        if marketplace > 0 {
+            self.share_marketplace_fees(token, marketplace, nonce);
+        }
+        self.transfer_or_save_payment(maker, token, nonce, &maker_refund);
+        self.transfer_or_save_payment(taker, token, nonce, &seller);
+    }
+
+    // Takes the swap royalties of `nfts` from the receiver's free EGLD deposit and pays their creators
+    fn charge_swap_royalties(
+        &self,
+        receiver: &ManagedAddress,
+        nfts: &ManagedVec<EsdtTokenPayment>,
+    ) {
+        let egld = EgldOrEsdtTokenIdentifier::egld();
+        for nft in nfts.iter() {
+            let royalty = self.swap_royalty(&nft.token_identifier).get() * &nft.amount;
+            if royalty == 0 {
+                continue;
+            }
+            self.has_free_balance_and_deduct(receiver, &egld, 0, &royalty);
+            self.pay_swap_royalty(&nft, royalty);
+        }
+    }
+
+    // Pays the maker's swap royalties out of the reserve, a royalty raised since creation is
+    // capped by what was set aside and the rest goes back to the maker's deposit
+    fn settle_royalty_reserve(
+        &self,
+        maker: &ManagedAddress,
+        reserve: &BigUint,
+        nfts: &ManagedVec<EsdtTokenPayment>,
+    ) {
+        let mut remaining = reserve.clone();
+        for nft in nfts.iter() {
+            let mut royalty = self.swap_royalty(&nft.token_identifier).get() * &nft.amount;
+            if royalty > remaining {
+                royalty = remaining.clone();
+            }
+            if royalty == 0 {
+                continue;
+            }
+            remaining -= &royalty;
+            self.pay_swap_royalty(&nft, royalty);
+        }
+        if remaining > 0 {
+            self.credit_balance(
+                maker,
+                EgldOrEsdtTokenPayment::new(EgldOrEsdtTokenIdentifier::egld(), 0, remaining),
+            );
+        }
+    }
+
+    fn pay_swap_royalty(&self, nft: &EsdtTokenPayment, royalty: BigUint) {
+        let nft_info = self.get_nft_info(&nft.token_identifier, nft.token_nonce);
+        let royalty_splits = self
+            .get_collection_config(&nft.token_identifier)
+            .map(|config| config.royalty_splits)
+            .unwrap_or_default();
+        self.share_royalties(
+            &nft_info.creator,
+            &royalty_splits,
+            &EgldOrEsdtTokenIdentifier::egld(),
+            royalty,
+            0,
+        );
+    }
+
+    fn try_get_swap(&self, swap_id: u64) -> NftSwap<Self::Api> {
+        let map = self.swap_by_id(swap_id);
+        require!(!map.is_empty(), "Swap {} does not exist!", swap_id);
+        map.get()
+    }
+
+    fn remove_swap(&self, swap_id: u64, swap: &NftSwap<Self::Api>) {
+        self.swaps_by_wallet(&swap.maker).swap_remove(&swap_id);
+        self.swap_by_id(swap_id).clear();
+    }
+
+    #[view(getLastSwapId)]
+    #[storage_mapper("lastSwapId")]
+    fn last_swap_id(&self) -> SingleValueMapper<u64>;
+
+    #[view(getSwap)]
+    #[storage_mapper("swapById")]
+    fn swap_by_id(&self, swap_id: u64) -> SingleValueMapper<NftSwap<Self::Api>>;
+
+    #[view(getSwapRoyalty)]
+    #[storage_mapper("swapRoyalty")]
+    fn swap_royalty(&self, collection: &TokenIdentifier) -> SingleValueMapper<BigUint>;
+
+    #[view(getSwapsByWallet)]
+    #[storage_mapper("swapsByWallet")]
+    fn swaps_by_wallet(&self, address: &ManagedAddress) -> UnorderedSetMapper<u64>;
+}
@@ -3,15 +3,17 @@ multiversx_sc::derive_imports!();
 
 use core::convert::TryInto;
 
-use super::auction::{AuctionType, Offer, OfferStatus};
+use super::auction::{AuctionType, Feature, Offer, OfferStatus, OffersCursor};
 use crate::auction::GlobalOffer;
 use crate::common;
 use crate::events;
+use crate::fees;
 use crate::helpers;
 use crate::pools;
+use crate::staking;
 use crate::views;
 use crate::wrapping;
-use crate::{storage, NFT_AMOUNT};
+use crate::{storage, MAX_BULK_ITEMS, NFT_AMOUNT};
 
 #[multiversx_sc::module]
 pub trait CustomOffersModule:
@@ -22,14 +24,24 @@ pub trait CustomOffersModule:
     + common::CommonModule
     + wrapping::WrappingModule
     + pools::PoolsModule
+    + staking::StakingModule
+    + fees::FeesModule
+    + crate::referrals::ReferralsModule
 {
+    #[allow_multiple_var_args]
     #[payable("*")]
     #[endpoint(acceptOffer)]
-    fn accept_offer(&self, offer_id: u64, auction_id: OptionalValue<u64>) {
-        self.require_enabled();
+    fn accept_offer(
+        &self,
+        offer_id: u64,
+        auction_id: OptionalValue<u64>,
+        referrer: OptionalValue<ManagedAddress>,
+    ) {
+        self.require_feature_enabled(Feature::Offers);
         let (payment_token, payment_token_nonce, payment_amount) =
             self.call_value().egld_or_single_esdt().into_tuple();
         let mut offer = self.try_get_offer(offer_id);
+        self.require_collection_active(&offer.token_type);
         let current_time = self.blockchain().get_block_timestamp_seconds().as_u64_seconds();
         require!(
             current_time <= offer.deadline,
@@ -40,7 +52,7 @@ pub trait CustomOffersModule:
 
         // SECURITY FIX: Check if offer owner has been blacklisted since creating the offer
         require!(
-            !self.blacklist_wallets().contains(&offer.offer_owner),
+            !self.is_blacklisted(&offer.offer_owner),
             "Offer owner has been blacklisted!"
         );
 
@@ -97,6 +109,19 @@ pub trait CustomOffersModule:
 
         self.common_offer_remove(offer_id, &offer);
         self.emit_accept_offer_event(offer_id, &offer, &seller, auction_id_sent);
+        let mut bid_split_amounts = self.calculate_amount_split(
+            &offer.price,
+            &creator_royalties_percentage,
+            &offer.marketplace_cut_percentage,
+            self.get_collection_config(&offer.token_type),
+            &seller,
+        );
+        self.apply_referral(
+            &mut bid_split_amounts,
+            referrer.into_option().as_ref(),
+            &offer.offer_owner,
+            &seller,
+        );
         self.distribute_tokens_common(
             ManagedVec::from(EsdtTokenPayment::new(
                 offer.token_type.clone(),
@@ -108,11 +133,7 @@ pub trait CustomOffersModule:
             &nft_info.creator,
             &seller,
             &offer.offer_owner,
-            &self.calculate_amount_split(
-                &offer.price,
-                &creator_royalties_percentage,
-                self.get_collection_config(&offer.token_type),
-            ),
+            &bid_split_amounts,
             false,
         );
     }
@@ -178,6 +199,57 @@ pub trait CustomOffersModule:
         self.common_withdraw_offer(offer_id, &offer);
     }
 
+    // Returns where to resume from, `Done` once every offer and global offer was withdrawn
+    #[endpoint(withdrawAllOffers)]
+    fn withdraw_all_offers(&self, from: OptionalValue<OffersCursor>) -> OffersCursor {
+        self.require_enabled();
+        let caller = self.blockchain().get_caller();
+        let cursor = from.into_option().unwrap_or(OffersCursor::Offers(0));
+        let mut remaining = MAX_BULK_ITEMS;
+        match cursor {
+            OffersCursor::Done => return OffersCursor::Done,
+            OffersCursor::Offers(from) => {
+                let map_offers = self.offers_by_wallet(&caller);
+                let iter = if from == 0 || !map_offers.contains(&from) {
+                    map_offers.iter()
+                } else {
+                    map_offers.iter_from(&from)
+                };
+                let offer_ids: ManagedVec<u64> = iter.take(MAX_BULK_ITEMS + 1).collect();
+                for (index, offer_id) in offer_ids.iter().enumerate() {
+                    if index == MAX_BULK_ITEMS {
+                        return OffersCursor::Offers(offer_id);
+                    }
+                    let offer = self.try_get_offer(offer_id);
+                    self.common_withdraw_offer(offer_id, &offer);
+                }
+                remaining -= offer_ids.len();
+            }
+            OffersCursor::GlobalOffers(_) => {}
+        }
+
+        // Removal swaps the last entry into the freed slot, so the set is walked from the end
+        let map_global_offers = self.user_global_offers(&caller);
+        let mut index = match cursor {
+            OffersCursor::GlobalOffers(from) => map_global_offers.get_index(&from),
+            _ => 0,
+        };
+        if index == 0 {
+            index = map_global_offers.len();
+        }
+        while index > 0 {
+            let offer_id = map_global_offers.get_by_index(index);
+            if remaining == 0 {
+                return OffersCursor::GlobalOffers(offer_id);
+            }
+            let offer = self.try_get_global_offer(offer_id);
+            self.common_global_offer_remove(&offer, true);
+            remaining -= 1;
+            index -= 1;
+        }
+        OffersCursor::Done
+    }
+
     #[payable("EGLD")]
     #[endpoint(sendOffer)]
     fn send_offer(
@@ -190,7 +262,9 @@ pub trait CustomOffersModule:
         nft_amount: BigUint,
         deadline: u64,
     ) -> u64 {
-        self.require_enabled();
+        self.require_feature_enabled(Feature::Offers);
+        self.require_collection_active(&nft_type);
+        self.require_collection_allowed(&nft_type);
 
         require!(
             self.accepted_tokens().contains(&payment_token),
@@ -215,7 +289,7 @@ pub trait CustomOffersModule:
         );
 
         require!(
-            !self.blacklist_wallets().contains(&caller),
+            !self.is_blacklisted(&caller),
             "Your address was blacklisted, all your SCAM offers are lost!"
         );
         let map_offer_check = self.check_offer_sent(&caller, &nft_type, nft_nonce, &payment_token);
@@ -233,7 +307,7 @@ pub trait CustomOffersModule:
 
         require!(deadline > current_time, "Deadline can't be in the past!");
 
-        let marketplace_cut_percentage = self.bid_cut_percentage().get();
+        let marketplace_cut_percentage = self.get_marketplace_cut(&payment_token);
 
         let offer_id = self.last_valid_offer_id().get() + 1;
         self.last_valid_offer_id().set(offer_id);
@@ -251,7 +325,14 @@ pub trait CustomOffersModule:
             offer_owner: caller.clone(),
             marketplace_cut_percentage,
             new_version: true,
+            committed: true,
         };
+        self.commit_funds(
+            &caller,
+            &offer.payment_token_type,
+            payment_token_nonce,
+            &offer.price,
+        );
         // Map ID with Offer Struct
         self.offer_by_id(offer_id).set(&offer);
         self.token_offers_ids(&nft_type, nft_nonce).insert(offer_id);
@@ -277,7 +358,9 @@ pub trait CustomOffersModule:
         quantity: BigUint,
         attributes: OptionalValue<ManagedBuffer>,
     ) -> u64 {
-        self.require_enabled();
+        self.require_feature_enabled(Feature::GlobalOffers);
+        self.require_collection_active(&collection);
+        self.require_collection_allowed(&collection);
 
         // SECURITY FIX: Validate quantity is greater than 0
         require!(quantity > 0, "Quantity must be greater than 0!");
@@ -298,7 +381,7 @@ pub trait CustomOffersModule:
             "You can not place over 250 global offers per wallet!"
         );
         require!(
-            !self.blacklist_wallets().contains(&caller),
+            !self.is_blacklisted(&caller),
             "Your address was blacklisted!"
         );
         let mut user_map = self.user_collection_global_offers(&caller, &collection);
@@ -319,8 +402,15 @@ pub trait CustomOffersModule:
             owner: caller.clone(),
             attributes: attributes.into_option(),
             new_version: true,
+            committed: true,
         };
         self.last_valid_global_offer_id().set(offer_id);
+        self.commit_funds(
+            &caller,
+            &offer.payment_token,
+            payment_nonce,
+            &(&offer.price * &offer.quantity),
+        );
 
         self.collection_global_offers(&collection).insert(offer_id);
         map_count_user_offers.insert(offer_id);
@@ -351,8 +441,9 @@ pub trait CustomOffersModule:
         offer_id: u64,
         auction_id_opt: OptionalValue<ManagedVec<u64>>,
         signature: OptionalValue<ManagedBuffer>,
+        referrer: OptionalValue<ManagedAddress>,
     ) {
-        self.require_enabled();
+        self.require_feature_enabled(Feature::GlobalOffers);
         let nfts = self.call_value().all_esdt_transfers().clone_value();
 
         require!(
@@ -366,10 +457,12 @@ pub trait CustomOffersModule:
         require!(!offer_map.is_empty(), "This offer is already removed!");
         let seller = self.blockchain().get_caller();
         let mut offer = offer_map.get();
+        self.require_collection_active(&offer.collection);
+        self.require_collection_allowed(&offer.collection);
 
         // SECURITY FIX: Check if offer owner has been blacklisted since creating the offer
         require!(
-            !self.blacklist_wallets().contains(&offer.owner),
+            !self.is_blacklisted(&offer.owner),
             "Offer owner has been blacklisted!"
         );
 
@@ -406,6 +499,10 @@ pub trait CustomOffersModule:
                 auction.auction_type == AuctionType::Nft,
                 "Cannot accept offers for auctions, just for listings with a fixed price!"
             );
+            require!(
+                auction.bundle_items.is_empty(),
+                "Offers can't be accepted for bundle listings!"
+            );
 
             require!(
                 offer.owner != auction.original_owner,
@@ -495,6 +592,19 @@ pub trait CustomOffersModule:
             &auctions_ids,
         );
 
+        let mut bid_split_amounts = self.calculate_amount_split(
+            to_deduct_payment_amount,
+            &last_nft_info.royalties,
+            &self.get_marketplace_cut(&offer.payment_token),
+            self.get_collection_config(&offer.collection),
+            &seller,
+        );
+        self.apply_referral(
+            &mut bid_split_amounts,
+            referrer.into_option().as_ref(),
+            &offer.owner,
+            &seller,
+        );
         self.distribute_tokens_common(
             accepted_nfts,
             &offer.payment_token,
@@ -502,19 +612,122 @@ pub trait CustomOffersModule:
             &last_nft_info.creator,
             &seller,
             &offer.owner,
-            &self.calculate_amount_split(
-                to_deduct_payment_amount,
-                &last_nft_info.royalties,
-                self.get_collection_config(&offer.collection),
-            ),
+            &bid_split_amounts,
             false,
         );
 
         if offer.quantity != total_quantity_wanted {
+            if offer.committed {
+                self.release_funds(
+                    &offer.owner,
+                    &offer.payment_token,
+                    offer.payment_nonce,
+                    to_deduct_payment_amount,
+                );
+            }
             offer.quantity -= &total_quantity_wanted;
             offer_map.set(offer.clone());
         } else {
             self.common_global_offer_remove(&offer, false);
         }
     }
+
+    #[endpoint(pruneUnfundedOffers)]
+    fn prune_unfunded_offers(&self, offer_ids: MultiValueEncoded<u64>) {
+        require!(
+            offer_ids.len() <= MAX_BULK_ITEMS,
+            "Cannot prune more than 80 offers at once!"
+        );
+        for offer_id in offer_ids {
+            let map_offer = self.offer_by_id(offer_id);
+            if map_offer.is_empty() {
+                continue;
+            }
+            let offer = map_offer.get();
+            if self.is_offer_unfunded(&offer) {
+                self.common_withdraw_offer(offer_id, &offer);
+            }
+        }
+    }
+
+    #[endpoint(pruneUnfundedGlobalOffers)]
+    fn prune_unfunded_global_offers(&self, offer_ids: MultiValueEncoded<u64>) {
+        require!(
+            offer_ids.len() <= MAX_BULK_ITEMS,
+            "Cannot prune more than 80 offers at once!"
+        );
+        for offer_id in offer_ids {
+            let map_offer = self.global_offer(offer_id);
+            if map_offer.is_empty() {
+                continue;
+            }
+            let offer = map_offer.get();
+            if self.is_global_offer_unfunded(&offer) {
+                self.common_global_offer_remove(&offer, true);
+            }
+        }
+    }
+
+    #[view(getUnfundedOffers)]
+    fn get_unfunded_offers(&self, from: u64, size: usize) -> MultiValueEncoded<u64> {
+        let mut results = MultiValueEncoded::new();
+        let map_offers = self.offers();
+        let iter = if from == 0 {
+            map_offers.iter()
+        } else {
+            // Offers are inserted in id order, so a pruned cursor resumes at the next live id
+            let last_offer_id = self.last_valid_offer_id().get();
+            match (from..=last_offer_id).find(|offer_id| map_offers.contains(offer_id)) {
+                Some(offer_id) => map_offers.iter_from(&offer_id),
+                None => return results,
+            }
+        };
+        for offer_id in iter.take(size) {
+            let offer = self.offer_by_id(offer_id).get();
+            if self.is_offer_unfunded(&offer) {
+                results.push(offer_id);
+            }
+        }
+        results
+    }
+
+    #[view(getUnfundedGlobalOffers)]
+    fn get_unfunded_global_offers(&self, from: usize, size: usize) -> MultiValueEncoded<u64> {
+        let mut results = MultiValueEncoded::new();
+        if size == 0 {
+            return results;
+        }
+        let map_offers = self.global_offer_ids();
+        let start = if from == 0 { 1 } else { from };
+        let end = core::cmp::min(map_offers.len(), start + size - 1);
+        for index in start..=end {
+            let offer_id = map_offers.get_by_index(index);
+            let offer = self.global_offer(offer_id).get();
+            if self.is_global_offer_unfunded(&offer) {
+                results.push(offer_id);
+            }
+        }
+        results
+    }
+
+    fn is_offer_unfunded(&self, offer: &Offer<Self::Api>) -> bool {
+        offer.new_version
+            && !self.is_funded(
+                &offer.offer_owner,
+                &offer.payment_token_type,
+                offer.payment_token_nonce,
+                &offer.price,
+            )
+    }
+
+    // A global offer stays live while its owner can still cover at least one item
+    fn is_global_offer_unfunded(&self, offer: &GlobalOffer<Self::Api>) -> bool {
+        offer.new_version
+            && !self.is_funded(
+                &offer.owner,
+                &offer.payment_token,
+                offer.payment_nonce,
+                &offer.price,
+            )
+    }
 }
@@ -0,0 +1,297 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::auction::{Feature, SignedOffer, SignedOrder};
+
+#[multiversx_sc::module]
+pub trait OrdersModule:
+    crate::storage::StorageModule
+    + crate::helpers::HelpersModule
+    + crate::views::ViewsModule
+    + crate::events::EventsModule
+    + crate::common::CommonModule
+    + crate::wrapping::WrappingModule
+    + crate::pools::PoolsModule
+    + crate::staking::StakingModule
+    + crate::fees::FeesModule
+    + crate::referrals::ReferralsModule
+{
+    #[payable("*")]
+    #[endpoint(escrowNfts)]
+    fn escrow_nfts(&self) {
+        self.require_feature_enabled(Feature::Listing);
+        let caller = self.blockchain().get_caller();
+        require!(
+            !self.is_blacklisted(&caller),
+            "Your address was blacklisted!"
+        );
+        let payments = self.call_value().all_esdt_transfers();
+        require!(!payments.is_empty(), "No NFTs sent!");
+        for payment in payments.iter() {
+            require!(
+                payment.token_nonce > 0,
+                "Only Semi-Fungible and Non-Fungible tokens can be escrowed!"
+            );
+            self.require_collection_active(&payment.token_identifier);
+            self.require_collection_allowed(&payment.token_identifier);
+            let map_escrow =
+                self.escrow_balance(&caller, &payment.token_identifier, payment.token_nonce);
+            map_escrow.update(|amt| *amt += &payment.amount);
+            self.emit_escrow_event(
+                &caller,
+                &payment.token_identifier,
+                payment.token_nonce,
+                &map_escrow.get(),
+            );
+        }
+    }
+
+    #[endpoint(withdrawEscrow)]
+    fn withdraw_escrow(&self, collection: TokenIdentifier, nonce: u64, amount: BigUint) {
+        let caller = self.blockchain().get_caller();
+        let map_escrow = self.escrow_balance(&caller, &collection, nonce);
+        let balance = map_escrow.get();
+        require!(amount > 0, "Amount must be higher than 0!");
+        require!(balance >= amount, "Not enough tokens in escrow!");
+        map_escrow.set(&(&balance - &amount));
+        self.tx()
+            .to(&caller)
+            .single_esdt(&collection, nonce, &amount)
+            .transfer();
+        self.emit_escrow_event(&caller, &collection, nonce, &map_escrow.get());
+    }
+
+    #[endpoint(cancelSignedOrders)]
+    fn cancel_signed_orders(&self, order_nonces: MultiValueEncoded<u64>) {
+        let caller = self.blockchain().get_caller();
+        for order_nonce in order_nonces {
+            self.order_nonce_used(&caller, order_nonce).set(true);
+            self.emit_order_cancel_event(&caller, order_nonce);
+        }
+    }
+
+    // Every order signed with a nonce below `min_order_nonce` becomes unusable
+    #[endpoint(invalidateSignedOrders)]
+    fn invalidate_signed_orders(&self, min_order_nonce: u64) {
+        let caller = self.blockchain().get_caller();
+        let map_min_nonce = self.min_order_nonce(&caller);
+        require!(
+            min_order_nonce > map_min_nonce.get(),
+            "The order nonce can only be increased!"
+        );
+        map_min_nonce.set(min_order_nonce);
+        self.emit_order_invalidate_event(&caller, min_order_nonce);
+    }
+
+    #[payable("*")]
+    #[endpoint(buySignedOrder)]
+    fn buy_signed_order(&self, order: SignedOrder<Self::Api>, signature: ManagedBuffer) {
+        self.require_feature_enabled(Feature::Buying);
+        let (payment_token, payment_nonce, payment_amount) =
+            self.call_value().egld_or_single_esdt().into_tuple();
+        let buyer = self.blockchain().get_caller();
+        let current_time = self
+            .blockchain()
+            .get_block_timestamp_seconds()
+            .as_u64_seconds();
+
+        require!(order.seller != buyer, "Can't buy your own order!");
+        require!(
+            !self.is_blacklisted(&order.seller),
+            "The seller address was blacklisted!"
+        );
+        self.require_collection_active(&order.collection);
+        self.require_collection_allowed(&order.collection);
+        require!(current_time < order.deadline, "The order has expired!");
+        require!(order.quantity > 0, "Quantity must be greater than 0!");
+        require!(
+            self.accepted_tokens().contains(&order.payment_token),
+            "The payment token is not whitelisted!"
+        );
+        require!(
+            payment_token == order.payment_token
+                && payment_nonce == order.payment_nonce
+                && payment_amount == order.price,
+            "Wrong amount paid, must pay equal to the order price!"
+        );
+
+        let map_used = self.order_nonce_used(&order.seller, order.order_nonce);
+        require!(
+            order.order_nonce >= self.min_order_nonce(&order.seller).get() && !map_used.get(),
+            "The order was cancelled or already filled!"
+        );
+
+        let mut data = ManagedBuffer::new();
+        data.append(self.blockchain().get_sc_address().as_managed_buffer());
+        require!(order.dep_encode(&mut data).is_ok(), "Invalid order!");
+        self.crypto()
+            .verify_ed25519(order.seller.as_managed_buffer(), &data, &signature);
+
+        let map_escrow = self.escrow_balance(&order.seller, &order.collection, order.token_nonce);
+        let escrowed = map_escrow.get();
+        require!(escrowed >= order.quantity, "Not enough tokens in escrow!");
+        map_escrow.set(&(&escrowed - &order.quantity));
+        map_used.set(true);
+
+        let nft_info = self.get_nft_info(&order.collection, order.token_nonce);
+        let bid_split_amounts = self.calculate_amount_split(
+            &order.price,
+            &nft_info.royalties,
+            &self.get_marketplace_cut(&order.payment_token),
+            self.get_collection_config(&order.collection),
+            &order.seller,
+        );
+        self.emit_order_fill_event(&order, &buyer);
+        self.distribute_tokens_common(
+            ManagedVec::from(EsdtTokenPayment::new(
+                order.collection.clone(),
+                order.token_nonce,
+                order.quantity.clone(),
+            )),
+            &order.payment_token,
+            order.payment_nonce,
+            &nft_info.creator,
+            &order.seller,
+            &buyer,
+            &bid_split_amounts,
+            false,
+        );
+    }
+
+    #[payable("*")]
+    #[endpoint(acceptSignedOffer)]
+    fn accept_signed_offer(&self, offer: SignedOffer<Self::Api>, signature: ManagedBuffer) {
+        self.require_feature_enabled(Feature::Offers);
+        let nft = self.call_value().single_esdt().clone();
+        let seller = self.blockchain().get_caller();
+        let current_time = self
+            .blockchain()
+            .get_block_timestamp_seconds()
+            .as_u64_seconds();
+
+        require!(offer.buyer != seller, "Cannot accept your own offer!");
+        require!(
+            !self.is_blacklisted(&offer.buyer),
+            "Offer owner has been blacklisted!"
+        );
+        self.require_collection_active(&offer.collection);
+        self.require_collection_allowed(&offer.collection);
+        require!(
+            current_time <= offer.deadline,
+            "Cannot accept the offer after deadline!"
+        );
+        require!(
+            nft.token_identifier == offer.collection,
+            "The token sent is not matching the offer!"
+        );
+        require!(
+            offer.token_nonce == 0 || nft.token_nonce == offer.token_nonce,
+            "The nonce used is not matching the offer!"
+        );
+        require!(
+            nft.amount == offer.quantity,
+            "The quantity sent is not matching the offer!"
+        );
+        require!(
+            offer.counter == self.signed_offer_counter(&offer.buyer).get(),
+            "The offer was cancelled!"
+        );
+
+        let data = self.signed_offer_data(&offer);
+        let offer_hash = self.crypto().keccak256(&data);
+        let map_used = self.signed_offer_used(&offer.buyer, &offer_hash);
+        require!(
+            !map_used.get(),
+            "The offer was cancelled or already accepted!"
+        );
+        self.crypto()
+            .verify_ed25519(offer.buyer.as_managed_buffer(), &data, &signature);
+        map_used.set(true);
+
+        self.has_free_balance_and_deduct(
+            &offer.buyer,
+            &offer.payment_token,
+            offer.payment_nonce,
+            &offer.price,
+        );
+
+        let nft_info = self.get_nft_info(&offer.collection, nft.token_nonce);
+        let bid_split_amounts = self.calculate_amount_split(
+            &offer.price,
+            &nft_info.royalties,
+            &self.get_marketplace_cut(&offer.payment_token),
+            self.get_collection_config(&offer.collection),
+            &seller,
+        );
+        self.emit_signed_offer_accept_event(&offer, &seller, nft.token_nonce);
+        self.distribute_tokens_common(
+            ManagedVec::from(nft),
+            &offer.payment_token,
+            offer.payment_nonce,
+            &nft_info.creator,
+            &seller,
+            &offer.buyer,
+            &bid_split_amounts,
+            false,
+        );
+    }
+
+    #[endpoint(cancelSignedOffer)]
+    fn cancel_signed_offer(&self, offer: SignedOffer<Self::Api>) {
+        let caller = self.blockchain().get_caller();
+        require!(offer.buyer == caller, "Only the offer owner can cancel it!");
+        let offer_hash = self.crypto().keccak256(self.signed_offer_data(&offer));
+        self.signed_offer_used(&caller, &offer_hash).set(true);
+        self.emit_signed_offer_cancel_event(&caller, &offer_hash);
+    }
+
+    // Bumping the counter voids every offer signed with the previous value
+    #[endpoint(cancelAllSignedOffers)]
+    fn cancel_all_signed_offers(&self) -> u64 {
+        let caller = self.blockchain().get_caller();
+        let map_counter = self.signed_offer_counter(&caller);
+        map_counter.update(|counter| *counter += 1);
+        self.emit_signed_offer_counter_event(&caller, map_counter.get());
+        map_counter.get()
+    }
+
+    fn signed_offer_data(&self, offer: &SignedOffer<Self::Api>) -> ManagedBuffer {
+        let mut data = ManagedBuffer::new();
+        data.append(self.blockchain().get_sc_address().as_managed_buffer());
+        require!(offer.dep_encode(&mut data).is_ok(), "Invalid offer!");
+        data
+    }
+
+    #[view(getSignedOfferCounter)]
+    #[storage_mapper("signedOfferCounter")]
+    fn signed_offer_counter(&self, buyer: &ManagedAddress) -> SingleValueMapper<u64>;
+
+    #[view(isSignedOfferUsed)]
+    #[storage_mapper("signedOfferUsed")]
+    fn signed_offer_used(
+        &self,
+        buyer: &ManagedAddress,
+        offer_hash: &ManagedByteArray<32>,
+    ) -> SingleValueMapper<bool>;
+
+    #[view(getEscrowBalance)]
+    #[storage_mapper("escrowBalance")]
+    fn escrow_balance(
+        &self,
+        seller: &ManagedAddress,
+        collection: &TokenIdentifier,
+        nonce: u64,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[view(getMinOrderNonce)]
+    #[storage_mapper("minOrderNonce")]
+    fn min_order_nonce(&self, seller: &ManagedAddress) -> SingleValueMapper<u64>;
+
+    #[view(isOrderNonceUsed)]
+    #[storage_mapper("orderNonceUsed")]
+    fn order_nonce_used(
+        &self,
+        seller: &ManagedAddress,
+        order_nonce: u64,
+    ) -> SingleValueMapper<bool>;
+}
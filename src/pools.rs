@@ -1,54 +1,200 @@
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
+use crate::auction::Feature;
+
 #[multiversx_sc::module]
-pub trait PoolsModule: crate::storage::StorageModule + crate::events::EventsModule {
+pub trait PoolsModule:
+    crate::storage::StorageModule
+    + crate::views::ViewsModule
+    + crate::events::EventsModule
+    + crate::staking::StakingModule
+{
     #[payable("EGLD")]
     #[endpoint(deposit)]
     fn deposit(&self) {
+        let caller = self.blockchain().get_caller();
+        self.deposit_common(&caller);
+    }
+
+    #[payable("EGLD")]
+    #[endpoint(depositFor)]
+    fn deposit_for(&self, beneficiary: ManagedAddress) {
+        require!(
+            !self.is_blacklisted(&self.blockchain().get_caller()),
+            "Your address was blacklisted!"
+        );
+        require!(
+            !self.is_blacklisted(&beneficiary),
+            "The beneficiary address was blacklisted!"
+        );
+        self.deposit_common(&beneficiary);
+    }
+
+    fn deposit_common(&self, beneficiary: &ManagedAddress) {
         let payment = self.call_value().egld_or_single_esdt();
         if payment.amount > 0 {
+            require!(
+                !self.feature_paused(Feature::Deposits).get(),
+                "Deposits are currently paused!"
+            );
             let map_acc_tokens = self.accepted_tokens();
             require!(
                 map_acc_tokens.contains(&payment.token_identifier),
                 "The deposited token is not whitelisted!"
             );
-            let caller = self.blockchain().get_caller();
-            let map_user = self.user_funds(&caller, &payment.token_identifier, payment.token_nonce);
-            if map_user.is_empty() {
-                map_user.set(payment);
-            } else {
-                map_user.update(|f| f.amount += payment.amount);
-            }
-            self.emit_deposit_balance(&caller, &map_user.get());
+            self.credit_balance(beneficiary, payment);
         }
     }
 
     #[endpoint(withdrawDeposit)]
     fn withdraw_deposit(&self, token: &EgldOrEsdtTokenIdentifier, nonce: u64, amount: &BigUint) {
         let caller = self.blockchain().get_caller();
-        let map_user = self.user_funds(&caller, token, nonce);
-        if !map_user.is_empty() {
-            let balance = map_user.get();
+        let balance = self.get_balance(&caller, token, nonce);
+        if balance > 0 {
             require!(
-                &balance.amount >= amount,
+                &balance >= amount,
                 "Your balance is under the requested amount!",
             );
-            let clear = &balance.amount == amount;
-            if clear {
-                map_user.clear();
-                self.emit_deposit_balance(
-                    &caller,
-                    &EgldOrEsdtTokenPayment::new(token.clone(), nonce, BigUint::zero()),
+            if self.strict_balance_mode().get() {
+                let committed = self.committed_funds(&caller, token, nonce).get();
+                require!(
+                    &balance - amount >= committed,
+                    "Cannot withdraw funds committed to active offers!"
                 );
-            } else {
-                map_user.update(|f| f.amount -= amount);
-                self.emit_deposit_balance(&caller, &map_user.get());
             }
+            self.deduct_balance(&caller, token, nonce, amount);
             self.send().direct(&caller, token, nonce, amount);
         }
     }
 
+    #[endpoint(setYieldMode)]
+    fn set_yield_mode(&self, enabled: bool) {
+        let caller = self.blockchain().get_caller();
+        let map_mode = self.yield_mode(&caller);
+        if map_mode.get() == enabled {
+            return;
+        }
+        let egld = EgldOrEsdtTokenIdentifier::egld();
+        if enabled {
+            require!(
+                !self.liquid_staking_sc().is_empty(),
+                "Yield mode is not available!"
+            );
+            map_mode.set(true);
+            let map_user = self.user_funds(&caller, &egld, 0);
+            if !map_user.is_empty() {
+                let funds = map_user.take();
+                self.stake_egld(&caller, funds.amount);
+            }
+        } else {
+            // Without enough buffer the shares stay in place and are redeemed when spent
+            map_mode.clear();
+            let received = self.unstake_all_egld(&caller);
+            self.add_plain_funds(&caller, &egld, 0, &received);
+        }
+        self.emit_yield_mode_event(&caller, enabled);
+        self.emit_deposit_balance(
+            &caller,
+            &EgldOrEsdtTokenPayment::new(egld.clone(), 0, self.get_balance(&caller, &egld, 0)),
+        );
+    }
+
+    fn credit_balance(&self, user: &ManagedAddress, payment: EgldOrEsdtTokenPayment) {
+        let (token, nonce, amount) = payment.into_tuple();
+        if self.is_yield_enabled(user, &token) {
+            self.stake_egld(user, amount);
+        } else {
+            self.add_plain_funds(user, &token, nonce, &amount);
+        }
+        if token.is_egld() {
+            self.sync_yield_reservation(user, false);
+        }
+        self.emit_deposit_balance(
+            user,
+            &EgldOrEsdtTokenPayment::new(
+                token.clone(),
+                nonce,
+                self.get_balance(user, &token, nonce),
+            ),
+        );
+    }
+
+    fn add_plain_funds(
+        &self,
+        user: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+        nonce: u64,
+        amount: &BigUint,
+    ) {
+        if amount == &0 {
+            return;
+        }
+        let map_user = self.user_funds(user, token, nonce);
+        if map_user.is_empty() {
+            map_user.set(EgldOrEsdtTokenPayment::new(
+                token.clone(),
+                nonce,
+                amount.clone(),
+            ));
+        } else {
+            map_user.update(|f| f.amount += amount);
+        }
+    }
+
+    // Deposited funds plus, for EGLD, the current value of the user's liquid staking shares
+    fn get_balance(
+        &self,
+        user: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+        nonce: u64,
+    ) -> BigUint {
+        let map_user = self.user_funds(user, token, nonce);
+        let mut balance = if map_user.is_empty() {
+            BigUint::zero()
+        } else {
+            map_user.get().amount
+        };
+        if token.is_egld() {
+            balance += self.staked_egld_value(user);
+        }
+        balance
+    }
+
+    fn deduct_balance(
+        &self,
+        user: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+        nonce: u64,
+        amount: &BigUint,
+    ) {
+        let map_user = self.user_funds(user, token, nonce);
+        let plain = if map_user.is_empty() {
+            BigUint::zero()
+        } else {
+            map_user.get().amount
+        };
+        if &plain > amount {
+            map_user.update(|f| f.amount -= amount);
+        } else {
+            map_user.clear();
+            if &plain < amount {
+                self.unstake_egld(user, &(amount - &plain));
+            }
+        }
+        if token.is_egld() {
+            self.sync_yield_reservation(user, false);
+        }
+        self.emit_deposit_balance(
+            user,
+            &EgldOrEsdtTokenPayment::new(
+                token.clone(),
+                nonce,
+                self.get_balance(user, token, nonce),
+            ),
+        );
+    }
+
     fn has_balance_and_deduct(
         &self,
         buyer: &ManagedAddress,
@@ -56,25 +202,33 @@ pub trait PoolsModule: crate::storage::StorageModule + crate::events::EventsModu
         nonce: u64,
         amount: &BigUint,
     ) {
-        let map_user = self.user_funds(buyer, token, nonce);
-        require!(!map_user.is_empty(), "This user has no balance deposited!");
+        self.has_balance(buyer, token, nonce, amount);
+        self.deduct_balance(buyer, token, nonce, amount);
+    }
 
-        let balance = map_user.get();
+    // Spends only what is not committed to on-chain offers
+    fn has_free_balance_and_deduct(
+        &self,
+        buyer: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+        nonce: u64,
+        amount: &BigUint,
+    ) {
         require!(
-            &balance.amount >= amount,
-            "Your balance is under the requested amount!",
+            &self.get_free_balance(buyer, token, nonce) >= amount,
+            "Your free balance is under the requested amount!"
         );
-        let clear = &balance.amount == amount;
-        if clear {
-            map_user.clear();
-            self.emit_deposit_balance(
-                buyer,
-                &EgldOrEsdtTokenPayment::new(token.clone(), nonce, BigUint::zero()),
-            );
-        } else {
-            map_user.update(|f| f.amount -= amount);
-            self.emit_deposit_balance(buyer, &map_user.get());
-        }
+        self.deduct_balance(buyer, token, nonce, amount);
+    }
+
+    fn is_funded(
+        &self,
+        buyer: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+        nonce: u64,
+        amount: &BigUint,
+    ) -> bool {
+        &self.get_balance(buyer, token, nonce) >= amount
     }
 
     fn has_balance(
@@ -84,16 +238,114 @@ pub trait PoolsModule: crate::storage::StorageModule + crate::events::EventsModu
         nonce: u64,
         amount: &BigUint,
     ) {
-        let map_user = self.user_funds(buyer, token, nonce);
-        require!(!map_user.is_empty(), "This user has no balance deposited!");
-
-        let balance = map_user.get();
+        let balance = self.get_balance(buyer, token, nonce);
+        require!(balance > 0, "This user has no balance deposited!");
         require!(
-            &balance.amount >= amount,
+            &balance >= amount,
             "Your balance is under the requested amount!",
         );
     }
 
+    fn commit_funds(
+        &self,
+        user: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+        nonce: u64,
+        amount: &BigUint,
+    ) {
+        self.committed_funds(user, token, nonce)
+            .update(|committed| *committed += amount);
+        if token.is_egld() {
+            self.sync_yield_reservation(user, true);
+        }
+    }
+
+    fn release_funds(
+        &self,
+        user: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+        nonce: u64,
+        amount: &BigUint,
+    ) {
+        let map_committed = self.committed_funds(user, token, nonce);
+        let committed = map_committed.get();
+        // Only offers flagged as committed are released, so the total covers the amount
+        if &committed <= amount {
+            map_committed.clear();
+        } else {
+            map_committed.set(committed - amount);
+        }
+        if token.is_egld() {
+            self.sync_yield_reservation(user, false);
+        }
+    }
+
+    // Sets aside the buffer paying out the staked part of the user's committed EGLD, new
+    // commitments are refused once the buffer can't back them
+    fn sync_yield_reservation(&self, user: &ManagedAddress, strict: bool) {
+        let egld = EgldOrEsdtTokenIdentifier::egld();
+        let map_reservation = self.yield_reservation(user);
+        let reserved = map_reservation.get();
+        let committed = self.committed_funds(user, &egld, 0).get();
+        let map_user = self.user_funds(user, &egld, 0);
+        let plain = if map_user.is_empty() {
+            BigUint::zero()
+        } else {
+            map_user.get().amount
+        };
+        let mut target = if committed > plain {
+            core::cmp::min(committed - plain, self.staked_egld_value(user))
+        } else {
+            BigUint::zero()
+        };
+        if target > reserved {
+            let available = self.available_yield_buffer(user);
+            if strict {
+                require!(
+                    available >= target,
+                    "Not enough liquid EGLD to back offers from staked funds, try again later!"
+                );
+            } else if target > available {
+                target = available;
+            }
+        }
+        if target == reserved {
+            return;
+        }
+        self.yield_buffer_reserved()
+            .update(|amt| *amt = &*amt + &target - &reserved);
+        map_reservation.set(target);
+    }
+
+    #[view(getFreeBalance)]
+    fn get_free_balance(
+        &self,
+        user: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+        nonce: u64,
+    ) -> BigUint {
+        let balance = self.get_balance(user, token, nonce);
+        let committed = self.committed_funds(user, token, nonce).get();
+        if balance > committed {
+            balance - committed
+        } else {
+            BigUint::zero()
+        }
+    }
+
+    #[view(getCommittedBalance)]
+    #[storage_mapper("committedBalance")]
+    fn committed_funds(
+        &self,
+        user: &ManagedAddress,
+        token: &EgldOrEsdtTokenIdentifier,
+        nonce: u64,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[view(isStrictBalanceMode)]
+    #[storage_mapper("strictBalanceMode")]
+    fn strict_balance_mode(&self) -> SingleValueMapper<bool>;
+
     #[view(userDeposit)]
     #[storage_mapper("userBalance")]
     fn user_funds(
@@ -0,0 +1,327 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::auction::{Feature, FeesDistribution, Raffle, RaffleStatus, TicketBatch};
+
+const MAX_RAFFLE_TICKETS: u64 = 10_000;
+
+#[multiversx_sc::module]
+pub trait RafflesModule:
+    crate::storage::StorageModule
+    + crate::helpers::HelpersModule
+    + crate::views::ViewsModule
+    + crate::events::EventsModule
+    + crate::common::CommonModule
+    + crate::wrapping::WrappingModule
+    + crate::pools::PoolsModule
+    + crate::staking::StakingModule
+    + crate::fees::FeesModule
+    + crate::referrals::ReferralsModule
+{
+    #[payable("*")]
+    #[endpoint(listRaffle)]
+    fn list_raffle(
+        &self,
+        payment_token: EgldOrEsdtTokenIdentifier,
+        ticket_price: BigUint,
+        max_tickets: u64,
+        min_tickets: u64,
+        deadline: u64,
+    ) -> u64 {
+        self.require_feature_enabled(Feature::Raffles);
+        let (collection, nonce, amount) = self.call_value().single_esdt().clone().into_tuple();
+        let caller = self.blockchain().get_caller();
+        let current_time = self
+            .blockchain()
+            .get_block_timestamp_seconds()
+            .as_u64_seconds();
+        require!(
+            !self.is_blacklisted(&caller),
+            "Your address was blacklisted!"
+        );
+        require!(
+            nonce > 0,
+            "Only Semi-Fungible and Non-Fungible tokens can be raffled"
+        );
+        self.require_collection_active(&collection);
+        self.require_collection_allowed(&collection);
+        require!(
+            self.accepted_tokens().contains(&payment_token),
+            "The payment token is not whitelisted!"
+        );
+        require!(ticket_price > 0, "Ticket price must be higher than 0!");
+        require!(
+            max_tickets > 0 && max_tickets <= MAX_RAFFLE_TICKETS,
+            "Max tickets must be between 1 and 10000!"
+        );
+        require!(
+            min_tickets > 0 && min_tickets <= max_tickets,
+            "Min tickets must be between 1 and max tickets!"
+        );
+        require!(deadline > current_time, "Deadline can't be in the past");
+
+        let nft_info = self.get_nft_info(&collection, nonce);
+        let creator_royalties_percentage = self.cap_royalties(
+            &collection,
+            nft_info.royalties,
+            &self.get_marketplace_cut(&payment_token),
+        );
+        let raffle_id = self.last_raffle_id().get() + 1;
+        self.last_raffle_id().set(raffle_id);
+        let raffle = Raffle {
+            owner: caller.clone(),
+            collection,
+            nonce,
+            amount,
+            payment_token,
+            ticket_price,
+            max_tickets,
+            min_tickets,
+            deadline,
+            creator: nft_info.creator,
+            creator_royalties_percentage,
+            tickets_sold: 0,
+            status: RaffleStatus::Active,
+            winner: ManagedAddress::zero(),
+        };
+        self.raffle_by_id(raffle_id).set(&raffle);
+        self.raffles_by_wallet(&caller).insert(raffle_id);
+        self.emit_raffle_create_event(raffle_id, &raffle);
+        raffle_id
+    }
+
+    #[payable("*")]
+    #[endpoint(buyTickets)]
+    fn buy_tickets(&self, raffle_id: u64, tickets: u64) {
+        self.require_feature_enabled(Feature::Raffles);
+        let mut raffle = self.try_get_raffle(raffle_id);
+        let payment = self.call_value().egld_or_single_esdt();
+        let caller = self.blockchain().get_caller();
+        let current_time = self
+            .blockchain()
+            .get_block_timestamp_seconds()
+            .as_u64_seconds();
+        require!(
+            raffle.status == RaffleStatus::Active,
+            "The raffle is not active!"
+        );
+        require!(current_time < raffle.deadline, "The raffle ended already!");
+        require!(raffle.owner != caller, "Cannot enter your own raffle!");
+        require!(
+            !self.is_blacklisted(&caller),
+            "Your address was blacklisted!"
+        );
+        require!(
+            tickets > 0 && raffle.tickets_sold + tickets <= raffle.max_tickets,
+            "Not enough tickets available!"
+        );
+        require!(
+            payment.token_identifier == raffle.payment_token
+                && payment.token_nonce == 0
+                && payment.amount == &raffle.ticket_price * tickets,
+            "Wrong amount paid, must pay equal to the tickets price!"
+        );
+
+        raffle.tickets_sold += tickets;
+        self.raffle_tickets(raffle_id).push(&TicketBatch {
+            buyer: caller.clone(),
+            end: raffle.tickets_sold,
+        });
+        self.raffle_participant_tickets(raffle_id, &caller)
+            .update(|qt| *qt += tickets);
+        self.raffle_by_id(raffle_id).set(&raffle);
+        self.emit_raffle_tickets_event(raffle_id, &caller, tickets, raffle.tickets_sold);
+    }
+
+    #[endpoint(drawRaffle)]
+    fn draw_raffle(&self, raffle_id: u64) {
+        let mut raffle = self.try_get_raffle(raffle_id);
+        let caller = self.blockchain().get_caller();
+        let current_time = self
+            .blockchain()
+            .get_block_timestamp_seconds()
+            .as_u64_seconds();
+        // Contracts could revert the draw until the outcome suits them
+        require!(
+            !self.blockchain().is_smart_contract(&caller),
+            "Only wallets can draw a raffle!"
+        );
+        require!(
+            raffle.status == RaffleStatus::Active,
+            "The raffle is not active!"
+        );
+        require!(
+            raffle.tickets_sold == raffle.max_tickets || current_time >= raffle.deadline,
+            "The raffle is still selling tickets!"
+        );
+        require!(
+            raffle.tickets_sold >= raffle.min_tickets,
+            "The minimum tickets were not sold, the raffle can only be refunded!"
+        );
+
+        // Fees raised since the listing can make the sale impossible, tickets are refunded instead
+        let proceeds = &raffle.ticket_price * raffle.tickets_sold;
+        let opt_split = self.try_calculate_amount_split(
+            &proceeds,
+            &raffle.creator_royalties_percentage,
+            &self.get_marketplace_cut(&raffle.payment_token),
+            self.get_collection_config(&raffle.collection),
+            &raffle.owner,
+        );
+        let split = match opt_split {
+            Some(split) => split,
+            None => {
+                self.fail_raffle(raffle_id, &mut raffle);
+                return;
+            }
+        };
+
+        let mut rand_source = RandomnessSource::new();
+        let winning_ticket = rand_source.next_u64_in_range(0, raffle.tickets_sold);
+        raffle.winner = self.find_ticket_owner(raffle_id, winning_ticket);
+        raffle.status = RaffleStatus::Drawn;
+        self.raffle_by_id(raffle_id).set(&raffle);
+        self.raffles_by_wallet(&raffle.owner)
+            .swap_remove(&raffle_id);
+        self.emit_raffle_draw_event(raffle_id, &raffle, winning_ticket);
+
+        self.settle_raffle(&raffle, &proceeds, split);
+        self.transfer_or_save_payment(
+            &raffle.winner,
+            &EgldOrEsdtTokenIdentifier::esdt(raffle.collection.clone()),
+            raffle.nonce,
+            &raffle.amount,
+        );
+    }
+
+    // The first call after a failed raffle returns the NFT, every participant reclaims their own tickets
+    #[endpoint(refundRaffle)]
+    fn refund_raffle(&self, raffle_id: u64) {
+        let mut raffle = self.try_get_raffle(raffle_id);
+        let caller = self.blockchain().get_caller();
+        let current_time = self
+            .blockchain()
+            .get_block_timestamp_seconds()
+            .as_u64_seconds();
+
+        if raffle.status == RaffleStatus::Active {
+            require!(
+                current_time >= raffle.deadline,
+                "The raffle is still active!"
+            );
+            require!(
+                raffle.tickets_sold < raffle.min_tickets,
+                "The minimum tickets were sold, the raffle has to be drawn!"
+            );
+            self.fail_raffle(raffle_id, &mut raffle);
+        }
+        require!(
+            raffle.status == RaffleStatus::Failed,
+            "The raffle was drawn, tickets can't be refunded!"
+        );
+
+        let map_tickets = self.raffle_participant_tickets(raffle_id, &caller);
+        let tickets = map_tickets.take();
+        if tickets > 0 {
+            self.transfer_or_save_payment(
+                &caller,
+                &raffle.payment_token,
+                0,
+                &(&raffle.ticket_price * tickets),
+            );
+            self.emit_raffle_refund_event(raffle_id, &caller, tickets);
+        }
+    }
+
+    fn fail_raffle(&self, raffle_id: u64, raffle: &mut Raffle<Self::Api>) {
+        raffle.status = RaffleStatus::Failed;
+        self.raffle_by_id(raffle_id).set(&*raffle);
+        self.raffles_by_wallet(&raffle.owner)
+            .swap_remove(&raffle_id);
+        self.emit_raffle_fail_event(raffle_id, raffle);
+        self.transfer_or_save_payment(
+            &raffle.owner,
+            &EgldOrEsdtTokenIdentifier::esdt(raffle.collection.clone()),
+            raffle.nonce,
+            &raffle.amount,
+        );
+    }
+
+    // Ticket batches are sorted by their end, the winner owns the first batch ending after the ticket
+    fn find_ticket_owner(&self, raffle_id: u64, ticket: u64) -> ManagedAddress {
+        let map_tickets = self.raffle_tickets(raffle_id);
+        let mut low = 1;
+        let mut high = map_tickets.len();
+        while low < high {
+            let middle = (low + high) / 2;
+            if map_tickets.get(middle).end > ticket {
+                high = middle;
+            } else {
+                low = middle + 1;
+            }
+        }
+        map_tickets.get(low).buyer
+    }
+
+    // Pays the ticket sales to the owner minus the marketplace fees and royalties
+    fn settle_raffle(
+        &self,
+        raffle: &Raffle<Self::Api>,
+        proceeds: &BigUint,
+        split: FeesDistribution<Self::Api>,
+    ) {
+        let token = &raffle.payment_token;
+        self.record_trade_volume(&raffle.owner, token, proceeds);
+
+        if split.extra > BigUint::zero() && !split.extra_address.is_zero() {
+            self.transfer_or_save_payment(&split.extra_address, token, 0, &split.extra);
+        }
+        if split.reverse_cut_fees {
+            self.transfer_or_save_payment(&raffle.winner, token, 0, &split.marketplace);
+        } else {
+            self.share_marketplace_fees(token, split.marketplace.clone(), 0);
+        }
+        if split.reverse_royalties {
+            self.transfer_or_save_payment(&raffle.winner, token, 0, &split.creator);
+        } else {
+            self.share_royalties(
+                &raffle.creator,
+                &split.royalty_splits,
+                token,
+                split.creator.clone(),
+                0,
+            );
+        }
+        self.transfer_or_save_payment(&raffle.owner, token, 0, &split.seller);
+    }
+
+    fn try_get_raffle(&self, raffle_id: u64) -> Raffle<Self::Api> {
+        let map = self.raffle_by_id(raffle_id);
+        require!(!map.is_empty(), "Raffle {} does not exist!", raffle_id);
+        map.get()
+    }
+
+    #[view(getLastRaffleId)]
+    #[storage_mapper("lastRaffleId")]
+    fn last_raffle_id(&self) -> SingleValueMapper<u64>;
+
+    #[view(getRaffle)]
+    #[storage_mapper("raffleById")]
+    fn raffle_by_id(&self, raffle_id: u64) -> SingleValueMapper<Raffle<Self::Api>>;
+
+    #[view(getRafflesByWallet)]
+    #[storage_mapper("rafflesByWallet")]
+    fn raffles_by_wallet(&self, address: &ManagedAddress) -> UnorderedSetMapper<u64>;
+
+    #[view(getRaffleTickets)]
+    #[storage_mapper("raffleTickets")]
+    fn raffle_tickets(&self, raffle_id: u64) -> VecMapper<TicketBatch<Self::Api>>;
+
+    #[view(getRaffleParticipantTickets)]
+    #[storage_mapper("raffleParticipantTickets")]
+    fn raffle_participant_tickets(
+        &self,
+        raffle_id: u64,
+        address: &ManagedAddress,
+    ) -> SingleValueMapper<u64>;
+}
@@ -0,0 +1,64 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::{auction::FeesDistribution, PERCENTAGE_TOTAL};
+
+#[multiversx_sc::module]
+pub trait ReferralsModule: crate::storage::StorageModule + crate::events::EventsModule {
+    // Carves the referrer's share out of the marketplace portion, unknown or self referrals are ignored
+    fn apply_referral(
+        &self,
+        split: &mut FeesDistribution<Self::Api>,
+        referrer: Option<&ManagedAddress>,
+        buyer: &ManagedAddress,
+        seller: &ManagedAddress,
+    ) {
+        let referrer = match referrer {
+            Some(referrer) => referrer,
+            None => return,
+        };
+        // Collections waiving the marketplace cut leave nothing to share with a referrer
+        if split.reverse_cut_fees || referrer.is_zero() || referrer == buyer || referrer == seller {
+            return;
+        }
+        let share = self.referrer_share(referrer).get();
+        if share == 0 {
+            return;
+        }
+        split.referral = &split.marketplace * &share / PERCENTAGE_TOTAL;
+        split.referrer = referrer.clone();
+    }
+
+    fn record_referral(
+        &self,
+        referrer: &ManagedAddress,
+        payment_token: &EgldOrEsdtTokenIdentifier,
+        amount: &BigUint,
+        trades: u64,
+    ) {
+        self.referral_earnings(referrer, payment_token)
+            .update(|earned| *earned += amount);
+        self.referral_trades(referrer)
+            .update(|total| *total += trades);
+    }
+
+    #[view(getReferrers)]
+    #[storage_mapper("referrers")]
+    fn referrers(&self) -> UnorderedSetMapper<ManagedAddress>;
+
+    #[view(getReferrerShare)]
+    #[storage_mapper("referrerShare")]
+    fn referrer_share(&self, referrer: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[view(getReferralEarnings)]
+    #[storage_mapper("referralEarnings")]
+    fn referral_earnings(
+        &self,
+        referrer: &ManagedAddress,
+        payment_token: &EgldOrEsdtTokenIdentifier,
+    ) -> SingleValueMapper<BigUint>;
+
+    #[view(getReferralTrades)]
+    #[storage_mapper("referralTrades")]
+    fn referral_trades(&self, referrer: &ManagedAddress) -> SingleValueMapper<u64>;
+}
@@ -0,0 +1,285 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::auction::{Feature, Rental};
+
+const MAX_RENTAL_DAYS: u64 = 365;
+const SECONDS_PER_DAY: u64 = 86_400;
+
+#[multiversx_sc::module]
+pub trait RentalsModule:
+    crate::storage::StorageModule
+    + crate::helpers::HelpersModule
+    + crate::views::ViewsModule
+    + crate::events::EventsModule
+    + crate::common::CommonModule
+    + crate::wrapping::WrappingModule
+    + crate::pools::PoolsModule
+    + crate::staking::StakingModule
+    + crate::fees::FeesModule
+    + crate::referrals::ReferralsModule
+{
+    #[payable("*")]
+    #[endpoint(listRental)]
+    fn list_rental(
+        &self,
+        payment_token: EgldOrEsdtTokenIdentifier,
+        daily_price: BigUint,
+        max_days: u64,
+        collateral: BigUint,
+    ) -> u64 {
+        self.require_feature_enabled(Feature::Rentals);
+        let (collection, nonce, amount) = self.call_value().single_esdt().clone().into_tuple();
+        let caller = self.blockchain().get_caller();
+        require!(
+            !self.is_blacklisted(&caller),
+            "Your address was blacklisted!"
+        );
+        require!(
+            nonce > 0,
+            "Only Semi-Fungible and Non-Fungible tokens can be rented"
+        );
+        self.require_collection_active(&collection);
+        self.require_collection_allowed(&collection);
+        require!(
+            self.accepted_tokens().contains(&payment_token),
+            "The payment token is not whitelisted!"
+        );
+        require!(daily_price > 0, "Daily price must be higher than 0!");
+        require!(
+            max_days > 0 && max_days <= MAX_RENTAL_DAYS,
+            "Max duration must be between 1 and 365 days!"
+        );
+
+        let nft_info = self.get_nft_info(&collection, nonce);
+        let creator_royalties_percentage = self.cap_royalties(
+            &collection,
+            nft_info.royalties,
+            &self.get_marketplace_cut(&payment_token),
+        );
+        let rental_id = self.last_rental_id().get() + 1;
+        self.last_rental_id().set(rental_id);
+        let rental = Rental {
+            owner: caller.clone(),
+            collection,
+            nonce,
+            amount,
+            payment_token,
+            daily_price,
+            max_days,
+            collateral,
+            creator: nft_info.creator,
+            creator_royalties_percentage,
+            renter: ManagedAddress::zero(),
+            rent_paid: BigUint::zero(),
+            expires_at: 0,
+        };
+        self.rental_by_id(rental_id).set(&rental);
+        self.rentals_by_wallet(&caller).insert(rental_id);
+        self.emit_rental_list_event(rental_id, &rental);
+        rental_id
+    }
+
+    // The renter pays the full rent plus the collateral upfront, both are held until the NFT is back
+    #[payable("*")]
+    #[endpoint(rent)]
+    fn rent(&self, rental_id: u64, days: u64) {
+        self.require_feature_enabled(Feature::Rentals);
+        let mut rental = self.try_get_rental(rental_id);
+        let payment = self.call_value().egld_or_single_esdt();
+        let caller = self.blockchain().get_caller();
+        let current_time = self
+            .blockchain()
+            .get_block_timestamp_seconds()
+            .as_u64_seconds();
+        require!(rental.renter.is_zero(), "The NFT is already rented!");
+        require!(rental.owner != caller, "Cannot rent your own NFT!");
+        require!(
+            !self.is_blacklisted(&caller) && !self.is_blacklisted(&rental.owner),
+            "The address was blacklisted!"
+        );
+        self.require_collection_active(&rental.collection);
+        require!(
+            days > 0 && days <= rental.max_days,
+            "Invalid rental duration!"
+        );
+        let rent_price = &rental.daily_price * days;
+        require!(
+            payment.token_identifier == rental.payment_token
+                && payment.token_nonce == 0
+                && payment.amount == &rent_price + &rental.collateral,
+            "Wrong payment, must pay the rent and the collateral!"
+        );
+
+        rental.renter = caller.clone();
+        rental.rent_paid = rent_price;
+        rental.expires_at = current_time + days * SECONDS_PER_DAY;
+        self.rental_by_id(rental_id).set(&rental);
+        self.rentals_by_renter(&caller).insert(rental_id);
+        self.emit_rental_rent_event(rental_id, &rental, days);
+        self.tx()
+            .to(&caller)
+            .single_esdt(&rental.collection, rental.nonce, &rental.amount)
+            .transfer();
+    }
+
+    #[payable("*")]
+    #[endpoint(returnRental)]
+    fn return_rental(&self, rental_id: u64) {
+        let mut rental = self.try_get_rental(rental_id);
+        let (collection, nonce, amount) = self.call_value().single_esdt().clone().into_tuple();
+        let caller = self.blockchain().get_caller();
+        require!(
+            rental.renter == caller,
+            "Only the renter can return the NFT!"
+        );
+        require!(
+            collection == rental.collection && nonce == rental.nonce && amount == rental.amount,
+            "The token sent is not matching the rental!"
+        );
+
+        // Every started day past the expiry is charged at the daily price out of the collateral
+        let current_time = self
+            .blockchain()
+            .get_block_timestamp_seconds()
+            .as_u64_seconds();
+        let mut late_fee = BigUint::zero();
+        if current_time > rental.expires_at {
+            let late_days = (current_time - rental.expires_at).div_ceil(SECONDS_PER_DAY);
+            late_fee = core::cmp::min(&rental.daily_price * late_days, rental.collateral.clone());
+        }
+        rental.rent_paid += &late_fee;
+
+        self.rentals_by_renter(&caller).swap_remove(&rental_id);
+        self.settle_rent(&rental);
+        self.transfer_or_save_payment(
+            &caller,
+            &rental.payment_token,
+            0,
+            &(&rental.collateral - &late_fee),
+        );
+        self.emit_rental_return_event(rental_id, &rental);
+
+        // The NFT is back in escrow and can be rented again
+        rental.renter = ManagedAddress::zero();
+        rental.rent_paid = BigUint::zero();
+        rental.expires_at = 0;
+        self.rental_by_id(rental_id).set(&rental);
+    }
+
+    #[endpoint(claimRentalCollateral)]
+    fn claim_rental_collateral(&self, rental_id: u64) {
+        let rental = self.try_get_rental(rental_id);
+        let caller = self.blockchain().get_caller();
+        let current_time = self
+            .blockchain()
+            .get_block_timestamp_seconds()
+            .as_u64_seconds();
+        require!(
+            rental.owner == caller,
+            "Only the owner can claim the collateral!"
+        );
+        require!(!rental.renter.is_zero(), "The NFT is not rented!");
+        require!(
+            current_time >= rental.expires_at,
+            "The rental period has not expired yet!"
+        );
+
+        self.remove_rental(rental_id, &rental);
+        self.rentals_by_renter(&rental.renter)
+            .swap_remove(&rental_id);
+        self.settle_rent(&rental);
+        self.transfer_or_save_payment(&caller, &rental.payment_token, 0, &rental.collateral);
+        self.emit_rental_forfeit_event(rental_id, &rental);
+    }
+
+    #[endpoint(cancelRental)]
+    fn cancel_rental(&self, rental_id: u64) {
+        let rental = self.try_get_rental(rental_id);
+        let caller = self.blockchain().get_caller();
+        require!(
+            rental.owner == caller,
+            "Only the owner can cancel the rental!"
+        );
+        require!(
+            rental.renter.is_zero(),
+            "Cannot cancel while the NFT is rented!"
+        );
+
+        self.remove_rental(rental_id, &rental);
+        self.emit_rental_cancel_event(rental_id, &rental);
+        self.tx()
+            .to(&caller)
+            .single_esdt(&rental.collection, rental.nonce, &rental.amount)
+            .transfer();
+    }
+
+    // Pays the rent to the owner minus the marketplace fees and royalties
+    fn settle_rent(&self, rental: &Rental<Self::Api>) {
+        let token = &rental.payment_token;
+        let opt_split = self.try_calculate_amount_split(
+            &rental.rent_paid,
+            &rental.creator_royalties_percentage,
+            &self.get_marketplace_cut(token),
+            self.get_collection_config(&rental.collection),
+            &rental.owner,
+        );
+        // Fees raised since the listing must not lock the NFT and the collateral
+        let split = match opt_split {
+            Some(split) => split,
+            None => {
+                self.transfer_or_save_payment(&rental.owner, token, 0, &rental.rent_paid);
+                return;
+            }
+        };
+
+        if split.extra > BigUint::zero() && !split.extra_address.is_zero() {
+            self.transfer_or_save_payment(&split.extra_address, token, 0, &split.extra);
+        }
+        if split.reverse_cut_fees {
+            self.transfer_or_save_payment(&rental.renter, token, 0, &split.marketplace);
+        } else {
+            self.share_marketplace_fees(token, split.marketplace.clone(), 0);
+        }
+        if split.reverse_royalties {
+            self.transfer_or_save_payment(&rental.renter, token, 0, &split.creator);
+        } else {
+            self.share_royalties(
+                &rental.creator,
+                &split.royalty_splits,
+                token,
+                split.creator.clone(),
+                0,
+            );
+        }
+        self.transfer_or_save_payment(&rental.owner, token, 0, &split.seller);
+    }
+
+    fn try_get_rental(&self, rental_id: u64) -> Rental<Self::Api> {
+        let map = self.rental_by_id(rental_id);
+        require!(!map.is_empty(), "Rental {} does not exist!", rental_id);
+        map.get()
+    }
+
+    fn remove_rental(&self, rental_id: u64, rental: &Rental<Self::Api>) {
+        self.rentals_by_wallet(&rental.owner)
+            .swap_remove(&rental_id);
+        self.rental_by_id(rental_id).clear();
+    }
+
+    #[view(getLastRentalId)]
+    #[storage_mapper("lastRentalId")]
+    fn last_rental_id(&self) -> SingleValueMapper<u64>;
+
+    #[view(getRental)]
+    #[storage_mapper("rentalById")]
+    fn rental_by_id(&self, rental_id: u64) -> SingleValueMapper<Rental<Self::Api>>;
+
+    #[view(getRentalsByWallet)]
+    #[storage_mapper("rentalsByWallet")]
+    fn rentals_by_wallet(&self, address: &ManagedAddress) -> UnorderedSetMapper<u64>;
+
+    #[view(getRentalsByRenter)]
+    #[storage_mapper("rentalsByRenter")]
+    fn rentals_by_renter(&self, address: &ManagedAddress) -> UnorderedSetMapper<u64>;
+}
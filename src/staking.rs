@@ -0,0 +1,235 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::liquid;
+
+// Precision of the cached EGLD value of one liquid staking share
+const YIELD_RATE_PRECISION: u64 = 1_000_000_000_000_000_000;
+
+#[multiversx_sc::module]
+pub trait StakingModule: crate::storage::StorageModule + crate::events::EventsModule {
+    // Liquid EGLD paying out redemptions of staked balances until their unbonding completes
+    #[only_owner]
+    #[payable("EGLD")]
+    #[endpoint(fundYieldBuffer)]
+    fn fund_yield_buffer(&self) {
+        let payment = self.call_value().egld().clone_value();
+        self.yield_buffer().update(|amt| *amt += &payment);
+        self.emit_yield_buffer_event(&self.yield_buffer().get());
+    }
+
+    // Sends the shares redeemed by users to the liquid staking contract, the unbonding NFT is kept
+    #[endpoint(unbondYieldShares)]
+    fn unbond_yield_shares(&self) {
+        let shares = self.pending_unbond_shares().take();
+        require!(shares > 0, "There are no shares to unbond!");
+        let back_transfers = self
+            .tx()
+            .to(self.liquid_staking_sc().get())
+            .typed(liquid::LiquidStakingProxy)
+            .un_delegate()
+            .single_esdt(&self.liquid_staking_token().get(), 0, &shares)
+            .returns(ReturnsBackTransfersReset)
+            .sync_call();
+
+        let mut found = false;
+        for payment in back_transfers.payments.iter() {
+            if payment.token_nonce > 0 && payment.token_identifier.is_esdt() {
+                self.yield_unbond_token()
+                    .set(payment.token_identifier.clone().unwrap_esdt());
+                self.yield_unbond_nonces().insert(payment.token_nonce);
+                found = true;
+            }
+        }
+        require!(found, "Liquid staking did not return an unbonding token!");
+    }
+
+    // Claims a matured unbonding NFT and refills the buffer with the EGLD received
+    #[endpoint(claimYieldUnbond)]
+    fn claim_yield_unbond(&self, nonce: u64) {
+        require!(
+            self.yield_unbond_nonces().swap_remove(&nonce),
+            "Unknown unbonding position!"
+        );
+        let token = self.yield_unbond_token().get();
+        let amount =
+            self.blockchain()
+                .get_esdt_balance(&self.blockchain().get_sc_address(), &token, nonce);
+        let back_transfers = self
+            .tx()
+            .to(self.liquid_staking_sc().get())
+            .typed(liquid::LiquidStakingProxy)
+            .withdraw()
+            .single_esdt(&token, nonce, &amount)
+            .returns(ReturnsBackTransfersReset)
+            .sync_call();
+
+        let received = back_transfers.egld_sum();
+        self.yield_buffer().update(|amt| *amt += &received);
+        self.emit_yield_buffer_event(&self.yield_buffer().get());
+    }
+
+    #[endpoint(refreshYieldRate)]
+    fn refresh_yield_rate(&self) {
+        let rate = self
+            .tx()
+            .to(self.liquid_staking_sc().get())
+            .typed(liquid::LiquidStakingProxy)
+            .get_egld_position_value(BigUint::from(YIELD_RATE_PRECISION))
+            .returns(ReturnsResult)
+            .sync_call_readonly();
+        require!(rate > 0, "Invalid liquid staking rate!");
+        self.yield_rate().set(rate);
+    }
+
+    fn is_yield_enabled(&self, user: &ManagedAddress, token: &EgldOrEsdtTokenIdentifier) -> bool {
+        token.is_egld() && self.yield_mode(user).get()
+    }
+
+    fn stake_egld(&self, user: &ManagedAddress, amount: BigUint) {
+        if amount == 0 {
+            return;
+        }
+        let back_transfers = self
+            .tx()
+            .to(self.liquid_staking_sc().get())
+            .typed(liquid::LiquidStakingProxy)
+            .delegate()
+            .egld(&amount)
+            .returns(ReturnsBackTransfersReset)
+            .sync_call();
+        let ls_token = EgldOrEsdtTokenIdentifier::esdt(self.liquid_staking_token().get());
+        let mut shares = BigUint::zero();
+        for payment in back_transfers.payments.iter() {
+            if payment.token_identifier == ls_token {
+                shares += &payment.amount;
+            }
+        }
+        require!(
+            shares > 0,
+            "Liquid staking did not return the expected token!"
+        );
+        // Every delegation refreshes the cached rate at no extra cost
+        self.yield_rate()
+            .set(&amount * YIELD_RATE_PRECISION / &shares);
+
+        self.yield_shares(user).update(|amt| *amt += &shares);
+        self.total_yield_shares().update(|amt| *amt += &shares);
+        self.emit_yield_shares_event(user, &self.yield_shares(user).get());
+    }
+
+    // Pays `amount` EGLD out of the buffer, the user's shares covering it are queued for unbonding
+    fn unstake_egld(&self, user: &ManagedAddress, amount: &BigUint) {
+        let owned_shares = self.yield_shares(user).get();
+        let mut shares = self.shares_for_egld(amount);
+        if shares > owned_shares {
+            // Rounding in the exchange rate can ask for a dust share above the position
+            require!(
+                &self.egld_for_shares(&owned_shares) >= amount,
+                "Your balance is under the requested amount!"
+            );
+            shares = owned_shares;
+        }
+        self.redeem_shares(user, &shares, amount);
+    }
+
+    // Redeems the whole position when the buffer can cover it, returning the EGLD released
+    fn unstake_all_egld(&self, user: &ManagedAddress) -> BigUint {
+        let shares = self.yield_shares(user).get();
+        let value = self.egld_for_shares(&shares);
+        if shares == 0 || self.available_yield_buffer(user) < value {
+            return BigUint::zero();
+        }
+        self.redeem_shares(user, &shares, &value);
+        value
+    }
+
+    fn redeem_shares(&self, user: &ManagedAddress, shares: &BigUint, amount: &BigUint) {
+        require!(
+            &self.available_yield_buffer(user) >= amount,
+            "Not enough liquid EGLD to redeem staked funds, try again later!"
+        );
+        self.yield_buffer().update(|amt| *amt -= amount);
+        let map_reservation = self.yield_reservation(user);
+        let reserved = map_reservation.get();
+        if reserved > 0 {
+            let used = core::cmp::min(reserved, amount.clone());
+            map_reservation.update(|amt| *amt -= &used);
+            self.yield_buffer_reserved().update(|amt| *amt -= &used);
+        }
+        self.pending_unbond_shares().update(|amt| *amt += shares);
+
+        self.yield_shares(user).update(|amt| *amt -= shares);
+        self.total_yield_shares().update(|amt| *amt -= shares);
+        self.emit_yield_shares_event(user, &self.yield_shares(user).get());
+    }
+
+    // The buffer set aside for other users' committed offers can't be redeemed from
+    fn available_yield_buffer(&self, user: &ManagedAddress) -> BigUint {
+        let buffer = self.yield_buffer().get() + self.yield_reservation(user).get();
+        let reserved = self.yield_buffer_reserved().get();
+        if buffer > reserved {
+            buffer - reserved
+        } else {
+            BigUint::zero()
+        }
+    }
+
+    fn staked_egld_value(&self, user: &ManagedAddress) -> BigUint {
+        let shares = self.yield_shares(user).get();
+        if shares == 0 {
+            return BigUint::zero();
+        }
+        self.egld_for_shares(&shares)
+    }
+
+    // Rounds up so the redemption never falls short of the requested amount
+    fn shares_for_egld(&self, amount: &BigUint) -> BigUint {
+        let rate = self.yield_rate().get();
+        require!(rate > 0, "Invalid liquid staking rate!");
+        (amount * YIELD_RATE_PRECISION + &rate - 1u32) / rate
+    }
+
+    fn egld_for_shares(&self, shares: &BigUint) -> BigUint {
+        shares * &self.yield_rate().get() / YIELD_RATE_PRECISION
+    }
+
+    #[view(isYieldMode)]
+    #[storage_mapper("yieldMode")]
+    fn yield_mode(&self, user: &ManagedAddress) -> SingleValueMapper<bool>;
+
+    #[view(getYieldShares)]
+    #[storage_mapper("yieldShares")]
+    fn yield_shares(&self, user: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[view(getTotalYieldShares)]
+    #[storage_mapper("totalYieldShares")]
+    fn total_yield_shares(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getYieldRate)]
+    #[storage_mapper("yieldRate")]
+    fn yield_rate(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getYieldBuffer)]
+    #[storage_mapper("yieldBuffer")]
+    fn yield_buffer(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getYieldBufferReserved)]
+    #[storage_mapper("yieldBufferReserved")]
+    fn yield_buffer_reserved(&self) -> SingleValueMapper<BigUint>;
+
+    #[view(getYieldReservation)]
+    #[storage_mapper("yieldReservation")]
+    fn yield_reservation(&self, user: &ManagedAddress) -> SingleValueMapper<BigUint>;
+
+    #[view(getPendingUnbondShares)]
+    #[storage_mapper("pendingUnbondShares")]
+    fn pending_unbond_shares(&self) -> SingleValueMapper<BigUint>;
+
+    #[storage_mapper("yieldUnbondToken")]
+    fn yield_unbond_token(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[view(getYieldUnbonds)]
+    #[storage_mapper("yieldUnbondNonces")]
+    fn yield_unbond_nonces(&self) -> UnorderedSetMapper<u64>;
+}
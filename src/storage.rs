@@ -7,6 +7,10 @@ pub trait StorageModule {
     #[storage_mapper("signer")]
     fn signer(&self) -> SingleValueMapper<ManagedAddress>;
 
+    #[view(getRoleMembers)]
+    #[storage_mapper("roleMembers")]
+    fn role_members(&self, role: Role) -> UnorderedSetMapper<ManagedAddress>;
+
     #[storage_mapper("wrappingSC")]
     fn wrapping(&self) -> SingleValueMapper<ManagedAddress>;
 
@@ -16,6 +20,15 @@ pub trait StorageModule {
     #[storage_mapper("aggregatorSC")]
     fn aggregator_sc(&self) -> SingleValueMapper<ManagedAddress>;
 
+    #[view(getLiquidStakingSC)]
+    #[storage_mapper("liquidStakingSC")]
+    fn liquid_staking_sc(&self) -> SingleValueMapper<ManagedAddress>;
+
+    #[view(getLiquidStakingToken)]
+    #[storage_mapper("liquidStakingToken")]
+    fn liquid_staking_token(&self) -> SingleValueMapper<TokenIdentifier>;
+
+    #[view(getXoxnoToken)]
     #[storage_mapper("xoxnoToken")]
     fn xoxno_token(&self) -> SingleValueMapper<TokenIdentifier>;
 
@@ -73,6 +86,10 @@ pub trait StorageModule {
     #[storage_mapper("blacklistWallets")]
     fn blacklist_wallets(&self) -> SetMapper<ManagedAddress>;
 
+    #[view(getBlacklistEntry)]
+    #[storage_mapper("blacklistEntry")]
+    fn blacklist_entry(&self, wallet: &ManagedAddress) -> SingleValueMapper<BlacklistEntry>;
+
     #[view(whitelistedContracts)]
     #[storage_mapper("whitelistedContracts")]
     fn whitelisted_contracts(&self) -> SetMapper<ManagedAddress>;
@@ -110,6 +127,26 @@ pub trait StorageModule {
     #[storage_mapper("offers")]
     fn offers(&self) -> SetMapper<u64>;
 
+    #[view(isFeaturePaused)]
+    #[storage_mapper("featurePaused")]
+    fn feature_paused(&self, feature: Feature) -> SingleValueMapper<bool>;
+
+    #[view(getHaltedCollections)]
+    #[storage_mapper("haltedCollections")]
+    fn halted_collections(&self) -> UnorderedSetMapper<TokenIdentifier>;
+
+    #[view(getDeniedCollections)]
+    #[storage_mapper("deniedCollections")]
+    fn denied_collections(&self) -> UnorderedSetMapper<TokenIdentifier>;
+
+    #[view(getAllowedCollections)]
+    #[storage_mapper("allowedCollections")]
+    fn allowed_collections(&self) -> UnorderedSetMapper<TokenIdentifier>;
+
+    #[view(isCuratedMode)]
+    #[storage_mapper("curatedMode")]
+    fn curated_mode(&self) -> SingleValueMapper<bool>;
+
     #[view(getStatus)]
     #[storage_mapper("status")]
     fn status(&self) -> SingleValueMapper<bool>;
@@ -174,6 +211,13 @@ pub trait StorageModule {
         ticker: &TokenIdentifier,
     ) -> SingleValueMapper<CollectionFeeConfig<Self::Api>>;
 
+    #[view(getCreatorSource)]
+    #[storage_mapper("creatorSource")]
+    fn creator_source(
+        &self,
+        ticker: &TokenIdentifier,
+    ) -> SingleValueMapper<CreatorSource<Self::Api>>;
+
     #[view(freezedAuctions)]
     #[storage_mapper("freezedAuctions")]
     fn freezed_auctions(&self) -> UnorderedSetMapper<u64>;
@@ -4,6 +4,38 @@ use crate::auction::*;
 
 #[multiversx_sc::module]
 pub trait ViewsModule: crate::storage::StorageModule {
+    #[view(isBlacklisted)]
+    fn is_blacklisted(&self, wallet: &ManagedAddress) -> bool {
+        if !self.blacklist_wallets().contains(wallet) {
+            return false;
+        }
+        let map_entry = self.blacklist_entry(wallet);
+        if map_entry.is_empty() {
+            return true;
+        }
+        let expires_at = map_entry.get().expires_at;
+        expires_at == 0
+            || self
+                .blockchain()
+                .get_block_timestamp_seconds()
+                .as_u64_seconds()
+                < expires_at
+    }
+
+    #[view(getPauseState)]
+    fn get_pause_state(&self) -> MultiValueEncoded<MultiValue2<Feature, bool>> {
+        let mut results = MultiValueEncoded::new();
+        for feature in ALL_FEATURES {
+            results.push((feature, self.feature_paused(feature).get()).into());
+        }
+        results
+    }
+
+    #[view(isCollectionHalted)]
+    fn is_collection_halted(&self, collection: &TokenIdentifier) -> bool {
+        self.halted_collections().contains(collection)
+    }
+
     #[view(getListingsCount)]
     fn get_listings_count(&self) -> usize {
         self.listings().len()
@@ -0,0 +1,109 @@
+mod common;
+
+use common::*;
+use multiversx_sc_scenario::imports::*;
+use xoxno_protocol::XOXNOProtocol;
+
+type Hash = ManagedByteArray<DebugApi, 32>;
+
+fn leaf(sc: &xoxno_protocol::ContractObj<DebugApi>, address: TestAddress) -> Hash {
+    sc.crypto()
+        .sha256(address.to_managed_address().as_managed_buffer())
+}
+
+// Hashes the pair in sorted order, as the contract does
+fn node(sc: &xoxno_protocol::ContractObj<DebugApi>, left: &Hash, right: &Hash) -> Hash {
+    let (first, second) = if left.to_byte_array() <= right.to_byte_array() {
+        (left, right)
+    } else {
+        (right, left)
+    };
+    let mut data = ManagedBuffer::new();
+    data.append(first.as_managed_buffer());
+    data.append(second.as_managed_buffer());
+    sc.crypto().sha256(&data)
+}
+
+// Lists NFT #1 of the seller at 10_000 EGLD for an allowlist of BUYER and OWNER
+fn list_allowlisted(world: &mut ScenarioWorld) {
+    set_nfts(world, SELLER, &[(NFT, 1, 1_000, CREATOR)]);
+    world
+        .tx()
+        .from(SELLER)
+        .to(XOXNO)
+        .esdt(TestEsdtTransfer(NFT, 1, 1))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            let root = node(&sc, &leaf(&sc, BUYER), &leaf(&sc, OWNER));
+            let mut listing = fixed_listing(NFT, 1, 10_000, EgldOrEsdtTokenIdentifier::egld());
+            listing.merkle_root = Some(root);
+            let mut listings = MultiValueEncoded::new();
+            listings.push(listing);
+            sc.listing(listings);
+        });
+}
+
+// Buys the listing as `buyer`, proving membership with OWNER's leaf as the only sibling
+fn buy_with_proof(world: &mut ScenarioWorld, buyer: TestAddress, expected_error: Option<&str>) {
+    let tx = world.tx().from(buyer).to(XOXNO).egld(10_000);
+    let call = |sc: xoxno_protocol::ContractObj<DebugApi>| {
+        let proof = ManagedVec::from_single_item(leaf(&sc, OWNER));
+        sc.buy(
+            1,
+            TokenIdentifier::from(NFT.as_str()),
+            1,
+            OptionalValue::None,
+            OptionalValue::None,
+            OptionalValue::Some(proof),
+        )
+    };
+    match expected_error {
+        Some(message) => tx
+            .returns(ExpectMessage(message))
+            .whitebox(xoxno_protocol::contract_obj, call),
+        None => tx.whitebox(xoxno_protocol::contract_obj, call),
+    }
+}
+
+#[test]
+fn allowlist_listing_buy_test() {
+    let mut world = setup();
+    list_allowlisted(&mut world);
+
+    buy_with_proof(&mut world, BUYER, None);
+
+    check_nft(&mut world, BUYER, NFT, 1);
+    world.check_account(SELLER).balance(8_750);
+    assert_eq!(royalties_of(&mut world, CREATOR), 1_000);
+}
+
+#[test]
+fn allowlist_listing_rejection_test() {
+    let mut world = setup();
+    list_allowlisted(&mut world);
+
+    // The same proof doesn't hash to the root from another leaf
+    buy_with_proof(
+        &mut world,
+        OTHER_BUYER,
+        Some("The buyer is not part of the allowlist!"),
+    );
+    buy_nft(
+        &mut world,
+        BUYER,
+        1,
+        (NFT, 1),
+        10_000,
+        Some("The buyer is not part of the allowlist!"),
+    );
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .egld(10_000)
+        .returns(ExpectMessage(
+            "Allowlist gated listings can't be bulk bought!",
+        ))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.bulk_buy(MultiValueEncoded::from(ManagedVec::from_single_item(1u64)));
+        });
+}
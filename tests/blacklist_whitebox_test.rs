@@ -0,0 +1,135 @@
+mod common;
+
+use common::*;
+use multiversx_sc_scenario::imports::*;
+use xoxno_protocol::{admin::AdminModule, storage::StorageModule, XOXNOProtocol};
+
+// The seller lists NFT #1 at a fixed 10_000 and auctions NFT #2 from 1_000, the buyer bids on it
+fn setup_listings() -> ScenarioWorld {
+    let mut world = setup();
+    set_nfts(
+        &mut world,
+        SELLER,
+        &[(NFT, 1, 1_000, CREATOR), (NFT, 2, 1_000, CREATOR)],
+    );
+    world
+        .tx()
+        .from(SELLER)
+        .to(XOXNO)
+        .multi_esdt(nft_payments(&[(NFT, 1), (NFT, 2)]))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            let mut auction = fixed_listing(NFT, 2, 1_000, EgldOrEsdtTokenIdentifier::egld());
+            auction.max_bid = BigUint::zero();
+            auction.bid = true;
+            auction.deadline = 1_000;
+            let mut listings = MultiValueEncoded::new();
+            listings.push(fixed_listing(
+                NFT,
+                1,
+                10_000,
+                EgldOrEsdtTokenIdentifier::egld(),
+            ));
+            listings.push(auction);
+            sc.listing(listings);
+        });
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .egld(1_000)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.bid(2, NFT.to_token_identifier(), 2)
+        });
+    world
+}
+
+fn purge(world: &mut ScenarioWorld, wallet: TestAddress) {
+    let mut cursor = u64::MAX;
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            cursor = sc.purge_blacklisted_wallet(wallet.to_managed_address(), OptionalValue::None);
+        });
+    assert_eq!(cursor, 0);
+}
+
+#[test]
+fn purge_blacklisted_wallet_test() {
+    let mut world = setup_listings();
+    world.check_account(BUYER).balance(1_000_000 - 1_000);
+
+    // A blacklisted bidder gets its bid back and the auction stays open
+    blacklist(&mut world, BUYER);
+    purge(&mut world, BUYER);
+    world.check_account(BUYER).balance(1_000_000);
+    world
+        .query()
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            let auction = sc.auction_by_id(2).get();
+            assert!(auction.current_winner.is_zero());
+            assert_eq!(auction.current_bid, BigUint::zero());
+        });
+
+    // A blacklisted seller gets every listing returned
+    blacklist(&mut world, SELLER);
+    purge(&mut world, SELLER);
+    check_nft(&mut world, SELLER, NFT, 1);
+    check_nft(&mut world, SELLER, NFT, 2);
+    world
+        .query()
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            assert!(sc
+                .listings_by_wallet(&SELLER.to_managed_address())
+                .is_empty());
+        });
+}
+
+#[test]
+fn blacklisted_seller_test() {
+    let mut world = setup_listings();
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .returns(ExpectMessage("The address is not blacklisted!"))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.purge_blacklisted_wallet(SELLER.to_managed_address(), OptionalValue::None);
+        });
+
+    // The entry expires at 100, the listing can't be bought until then
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.add_blacklist(
+                SELLER.to_managed_address(),
+                OptionalValue::Some(1),
+                OptionalValue::Some(100),
+            );
+        });
+    world
+        .tx()
+        .from(OTHER_BUYER)
+        .to(XOXNO)
+        .returns(ExpectMessage("You are not an admin!"))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.purge_blacklisted_wallet(SELLER.to_managed_address(), OptionalValue::None);
+        });
+    buy_nft(
+        &mut world,
+        OTHER_BUYER,
+        1,
+        (NFT, 1),
+        10_000,
+        Some("The seller address was blacklisted!"),
+    );
+
+    set_time(&mut world, 100);
+    buy_nft(&mut world, OTHER_BUYER, 1, (NFT, 1), 10_000, None);
+    check_nft(&mut world, OTHER_BUYER, NFT, 1);
+}
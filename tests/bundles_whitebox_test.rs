@@ -0,0 +1,97 @@
+mod common;
+
+use common::*;
+use multiversx_sc_scenario::imports::*;
+use xoxno_protocol::{
+    admin::AdminModule, auction::BundleListing, storage::StorageModule, XOXNOProtocol,
+};
+
+// Bundles one 5% OTHER item and two 10% NFT items at 10_000 EGLD, NFT #1 becomes its primary
+fn list_bundle(world: &mut ScenarioWorld) {
+    set_nfts(
+        world,
+        SELLER,
+        &[
+            (OTHER_NFT, 1, 500, OTHER_CREATOR),
+            (NFT, 1, 1_000, CREATOR),
+            (NFT, 2, 1_000, CREATOR),
+        ],
+    );
+    world
+        .tx()
+        .from(SELLER)
+        .to(XOXNO)
+        .multi_esdt(nft_payments(&[(OTHER_NFT, 1), (NFT, 1), (NFT, 2)]))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.list_bundle(BundleListing {
+                min_bid: BigUint::from(10_000u64),
+                max_bid: BigUint::from(10_000u64),
+                deadline: 0,
+                accepted_payment_token: EgldOrEsdtTokenIdentifier::egld(),
+                bid: false,
+                opt_start_time: 0,
+                reserved_buyer: None,
+            });
+        });
+}
+
+#[test]
+fn bundle_royalty_split_test() {
+    let mut world = setup();
+    list_bundle(&mut world);
+
+    buy_nft(&mut world, BUYER, 1, (NFT, 1), 10_000, None);
+
+    check_nft(&mut world, BUYER, NFT, 1);
+    check_nft(&mut world, BUYER, NFT, 2);
+    check_nft(&mut world, BUYER, OTHER_NFT, 1);
+    // The average royalty of the items (8.33%) is charged, each item taking its own royalty's share
+    world.check_account(SELLER).balance(8_917);
+    assert_eq!(royalties_of(&mut world, CREATOR), 667);
+    assert_eq!(royalties_of(&mut world, OTHER_CREATOR), 166);
+    assert_eq!(fees_of(&mut world), 250);
+}
+
+#[test]
+fn bundle_item_collection_checks_test() {
+    let mut world = setup();
+    list_bundle(&mut world);
+
+    // Halting or denylisting any collection of the bundle blocks the sale, not only the primary's
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.halt_collection(OTHER_NFT.to_token_identifier())
+        });
+    buy_nft(
+        &mut world,
+        BUYER,
+        1,
+        (NFT, 1),
+        10_000,
+        Some("Trading is halted for this collection!"),
+    );
+
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.halted_collections()
+                .swap_remove(&OTHER_NFT.to_token_identifier());
+            sc.add_denied_collections(MultiValueEncoded::from(ManagedVec::from_single_item(
+                OTHER_NFT.to_token_identifier(),
+            )))
+        });
+    buy_nft(
+        &mut world,
+        BUYER,
+        1,
+        (NFT, 1),
+        10_000,
+        Some("This collection is denylisted!"),
+    );
+    check_nft(&mut world, XOXNO, OTHER_NFT, 1);
+}
@@ -0,0 +1,160 @@
+mod common;
+
+use common::*;
+use multiversx_sc_scenario::imports::*;
+use xoxno_protocol::{
+    admin::AdminModule,
+    auction::{GovernanceAction, Role},
+    claims::ClaimsModule,
+    governance::GovernanceModule,
+    XOXNOProtocol,
+};
+
+const SELLER_CONTRACT: TestSCAddress = TestSCAddress::new("seller-contract");
+
+// A contract sells NFT #1 for 10_000, its 8_750 share is kept for it to claim
+fn sell_from_contract(world: &mut ScenarioWorld) {
+    world.account(SELLER).nonce(1);
+    world
+        .account(SELLER_CONTRACT)
+        .nonce(1)
+        .code(ACCUMULATOR_CODE_PATH)
+        .owner(OWNER)
+        .esdt_nft_all_properties(
+            NFT,
+            1,
+            1,
+            ManagedBuffer::new(),
+            1_000,
+            Some(CREATOR),
+            ManagedBuffer::new(),
+            Vec::<ManagedBuffer<StaticApi>>::new(),
+        );
+    world
+        .tx()
+        .from(SELLER_CONTRACT)
+        .to(XOXNO)
+        .single_esdt(&NFT.to_token_identifier(), 1, &BigUint::from(1u64))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            let mut listings = MultiValueEncoded::new();
+            listings.push(fixed_listing(
+                NFT,
+                1,
+                10_000,
+                EgldOrEsdtTokenIdentifier::egld(),
+            ));
+            sc.listing(listings);
+        });
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .egld(10_000)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            buy_call(&sc, 1, NFT, 1, None)
+        });
+    world.check_account(SELLER_CONTRACT).balance(0);
+}
+
+fn claim_for_contract(
+    world: &mut ScenarioWorld,
+    caller: TestAddress,
+    expected_error: Option<&str>,
+) {
+    let tx = world.tx().from(caller).to(XOXNO);
+    let call = |sc: xoxno_protocol::ContractObj<DebugApi>| {
+        sc.claim(OptionalValue::Some(SELLER_CONTRACT.to_managed_address()))
+    };
+    match expected_error {
+        Some(message) => tx
+            .returns(ExpectMessage(message))
+            .whitebox(xoxno_protocol::contract_obj, call),
+        None => tx.whitebox(xoxno_protocol::contract_obj, call),
+    }
+}
+
+#[test]
+fn claim_receiver_test() {
+    let mut world = setup();
+    sell_from_contract(&mut world);
+
+    world
+        .tx()
+        .from(SELLER_CONTRACT)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.set_claim_receiver(OptionalValue::Some(SELLER.to_managed_address()))
+        });
+    claim_for_contract(&mut world, SELLER, None);
+    world.check_account(SELLER).balance(8_750);
+
+    // Everything was paid out, a second claim sends nothing
+    claim_for_contract(&mut world, SELLER, None);
+    world.check_account(SELLER).balance(8_750);
+}
+
+#[test]
+fn claim_receiver_on_behalf_test() {
+    let mut world = setup();
+    sell_from_contract(&mut world);
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.grant_role(Role::ListingModerator, BUYER.to_managed_address())
+        });
+
+    // Only the contract itself sets its receiver directly, moderators included
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .returns(ExpectMessage(
+            "Only smart contracts can set a claim receiver!",
+        ))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.set_claim_receiver(OptionalValue::Some(BUYER.to_managed_address()))
+        });
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .returns(ExpectMessage("Endpoint can only be called by owner"))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.propose_action(GovernanceAction::SetClaimReceiver(
+                SELLER_CONTRACT.to_managed_address(),
+                Some(BUYER.to_managed_address()),
+            ));
+        });
+    claim_for_contract(
+        &mut world,
+        BUYER,
+        Some("You are not allowed to claim for this address!"),
+    );
+
+    // The owner's nomination waits out the timelock
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.set_claim_receiver_for(
+                SELLER_CONTRACT.to_managed_address(),
+                OptionalValue::Some(SELLER.to_managed_address()),
+            )
+        });
+    claim_for_contract(
+        &mut world,
+        SELLER,
+        Some("You are not allowed to claim for this address!"),
+    );
+    set_time(&mut world, 86_400);
+    world
+        .tx()
+        .from(SELLER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| sc.execute_action(1));
+    claim_for_contract(&mut world, SELLER, None);
+    world.check_account(SELLER).balance(8_750);
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use common::*;
+use multiversx_sc_scenario::imports::*;
+use xoxno_protocol::{admin::AdminModule, offers::CustomOffersModule, XOXNOProtocol};
+
+fn list(world: &mut ScenarioWorld, token: TestTokenIdentifier, expected_error: Option<&str>) {
+    let tx = world
+        .tx()
+        .from(SELLER)
+        .to(XOXNO)
+        .esdt(TestEsdtTransfer(token, 1, 1));
+    let call = |sc: xoxno_protocol::ContractObj<DebugApi>| {
+        let mut listings = MultiValueEncoded::new();
+        listings.push(fixed_listing(
+            token,
+            1,
+            10_000,
+            EgldOrEsdtTokenIdentifier::egld(),
+        ));
+        sc.listing(listings)
+    };
+    match expected_error {
+        Some(message) => tx
+            .returns(ExpectMessage(message))
+            .whitebox(xoxno_protocol::contract_obj, call),
+        None => tx.whitebox(xoxno_protocol::contract_obj, call),
+    }
+}
+
+fn setup_collections() -> ScenarioWorld {
+    let mut world = setup();
+    set_nfts(
+        &mut world,
+        SELLER,
+        &[(NFT, 1, 1_000, CREATOR), (OTHER_NFT, 1, 1_000, CREATOR)],
+    );
+    world
+}
+
+#[test]
+fn curated_mode_test() {
+    let mut world = setup_collections();
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.set_curated_mode(true);
+            sc.add_allowed_collections(MultiValueEncoded::from(ManagedVec::from_single_item(
+                NFT.to_token_identifier(),
+            )));
+        });
+
+    list(&mut world, NFT, None);
+    check_nft(&mut world, XOXNO, NFT, 1);
+    list(
+        &mut world,
+        OTHER_NFT,
+        Some("This collection is not allowlisted!"),
+    );
+    check_nft(&mut world, SELLER, OTHER_NFT, 1);
+}
+
+#[test]
+fn denied_collection_test() {
+    let mut world = setup_collections();
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .returns(ExpectMessage("You are not an admin!"))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.add_denied_collections(MultiValueEncoded::from(ManagedVec::from_single_item(
+                NFT.to_token_identifier(),
+            )))
+        });
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.add_denied_collections(MultiValueEncoded::from(ManagedVec::from_single_item(
+                NFT.to_token_identifier(),
+            )))
+        });
+
+    list(&mut world, NFT, Some("This collection is denylisted!"));
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .egld(1_000)
+        .returns(ExpectMessage("This collection is denylisted!"))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.send_offer(
+                EgldOrEsdtTokenIdentifier::egld(),
+                0,
+                BigUint::from(1_000u64),
+                NFT.to_token_identifier(),
+                1,
+                BigUint::from(1u64),
+                1_000,
+            );
+        });
+    list(&mut world, OTHER_NFT, None);
+}
@@ -0,0 +1,88 @@
+mod common;
+
+use common::*;
+use multiversx_sc_scenario::imports::*;
+use xoxno_protocol::{admin::AdminModule, offers::CustomOffersModule, pools::PoolsModule};
+
+fn withdraw_deposit(world: &mut ScenarioWorld, amount: u64, expected_error: Option<&str>) {
+    let tx = world.tx().from(BUYER).to(XOXNO);
+    let call = |sc: xoxno_protocol::ContractObj<DebugApi>| {
+        sc.withdraw_deposit(
+            &EgldOrEsdtTokenIdentifier::egld(),
+            0,
+            &BigUint::from(amount),
+        )
+    };
+    match expected_error {
+        Some(message) => tx
+            .returns(ExpectMessage(message))
+            .whitebox(xoxno_protocol::contract_obj, call),
+        None => tx.whitebox(xoxno_protocol::contract_obj, call),
+    }
+}
+
+fn set_strict_mode(world: &mut ScenarioWorld) {
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.set_strict_balance_mode(true)
+        });
+}
+
+#[test]
+fn committed_funds_release_test() {
+    let mut world = setup();
+    send_offer(&mut world, BUYER, 1, 1_000, 1_500);
+    send_offer(&mut world, BUYER, 2, 400, 0);
+    assert_eq!(deposit_of(&mut world, BUYER), (1_500, 1_400));
+    world
+        .query()
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            let free = sc.get_free_balance(
+                &BUYER.to_managed_address(),
+                &EgldOrEsdtTokenIdentifier::egld(),
+                0,
+            );
+            assert_eq!(free, BigUint::from(100u64));
+        });
+
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| sc.withdraw_offer(1));
+    assert_eq!(deposit_of(&mut world, BUYER), (1_500, 400));
+
+    // Outside strict mode committed funds stay withdrawable
+    withdraw_deposit(&mut world, 1_500, None);
+    assert_eq!(deposit_of(&mut world, BUYER), (0, 400));
+    world.check_account(BUYER).balance(1_000_000);
+}
+
+#[test]
+fn strict_mode_withdraw_test() {
+    let mut world = setup();
+    send_offer(&mut world, BUYER, 1, 1_000, 1_500);
+
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .returns(ExpectMessage("You are not an admin!"))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.set_strict_balance_mode(true)
+        });
+    set_strict_mode(&mut world);
+
+    withdraw_deposit(
+        &mut world,
+        501,
+        Some("Cannot withdraw funds committed to active offers!"),
+    );
+    withdraw_deposit(&mut world, 500, None);
+    assert_eq!(deposit_of(&mut world, BUYER), (1_000, 1_000));
+    world.check_account(BUYER).balance(1_000_000 - 1_000);
+}
@@ -0,0 +1,332 @@
+#![allow(dead_code)]
+
+use accumulator_mock::AccumulatorMock;
+use multiversx_sc_scenario::imports::*;
+use xoxno_protocol::{
+    admin::AdminModule, auction::BulkListing, offers::CustomOffersModule, pools::PoolsModule,
+    storage::StorageModule, XOXNOProtocol,
+};
+
+// Stands in for the fees accumulator, recording the fees per token and the royalties per creator
+pub mod accumulator_mock {
+    multiversx_sc::imports!();
+
+    #[multiversx_sc::contract]
+    pub trait AccumulatorMock {
+        #[init]
+        fn init(&self) {}
+
+        #[payable("*")]
+        #[endpoint(deposit)]
+        fn deposit(&self) {
+            let payment = self.call_value().egld_or_single_esdt();
+            self.fees(&payment.token_identifier)
+                .update(|amt| *amt += &payment.amount);
+        }
+
+        #[payable("*")]
+        #[endpoint(depositRoyalties)]
+        fn deposit_royalties(&self, creator: ManagedAddress) {
+            let payment = self.call_value().egld_or_single_esdt();
+            self.royalties(&creator)
+                .update(|amt| *amt += &payment.amount);
+        }
+
+        #[storage_mapper("fees")]
+        fn fees(&self, token: &EgldOrEsdtTokenIdentifier) -> SingleValueMapper<BigUint>;
+
+        #[storage_mapper("royalties")]
+        fn royalties(&self, creator: &ManagedAddress) -> SingleValueMapper<BigUint>;
+    }
+}
+
+pub const ACCUMULATOR_CODE_PATH: MxscPath = MxscPath::new("output/accumulator-mock.mxsc.json");
+pub const OWNER: TestAddress = TestAddress::new("owner");
+pub const SELLER: TestAddress = TestAddress::new("seller");
+pub const BUYER: TestAddress = TestAddress::new("buyer");
+pub const OTHER_BUYER: TestAddress = TestAddress::new("other-buyer");
+pub const CREATOR: TestAddress = TestAddress::new("creator");
+pub const OTHER_CREATOR: TestAddress = TestAddress::new("other-creator");
+pub const WRAPPING: TestAddress = TestAddress::new("wrapping");
+pub const AGGREGATOR: TestAddress = TestAddress::new("aggregator");
+pub const ACCUMULATOR: TestSCAddress = TestSCAddress::new("accumulator");
+pub const XOXNO: TestSCAddress = TestSCAddress::new("xoxno");
+pub const CODE_PATH: MxscPath = MxscPath::new("output/xoxno-protocol.mxsc.json");
+pub const NFT: TestTokenIdentifier = TestTokenIdentifier::new("NFT-123456");
+pub const OTHER_NFT: TestTokenIdentifier = TestTokenIdentifier::new("OTHER-123456");
+pub const WEGLD: TestTokenIdentifier = TestTokenIdentifier::new("WEGLD-abcdef");
+
+pub fn world() -> ScenarioWorld {
+    let mut blockchain = ScenarioWorld::new();
+    blockchain.register_contract(CODE_PATH, xoxno_protocol::ContractBuilder);
+    blockchain.register_contract(ACCUMULATOR_CODE_PATH, accumulator_mock::ContractBuilder);
+    blockchain
+}
+
+pub fn setup() -> ScenarioWorld {
+    setup_with_signer(OWNER)
+}
+
+pub fn setup_with_signer(signer: TestAddress) -> ScenarioWorld {
+    let mut world = world();
+    world.account(OWNER).nonce(1);
+    world.account(BUYER).nonce(1).balance(1_000_000u64);
+    world.account(OTHER_BUYER).nonce(1).balance(1_000_000u64);
+    world
+        .account(ACCUMULATOR)
+        .nonce(1)
+        .code(ACCUMULATOR_CODE_PATH)
+        .owner(OWNER);
+    world
+        .tx()
+        .from(OWNER)
+        .raw_deploy()
+        .code(CODE_PATH)
+        .new_address(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.init(
+                250,
+                signer.to_managed_address(),
+                WRAPPING.to_managed_address(),
+                WEGLD.to_token_identifier(),
+                AGGREGATOR.to_managed_address(),
+            );
+            sc.status().set(true);
+            sc.accepted_tokens()
+                .insert(EgldOrEsdtTokenIdentifier::egld());
+            sc.accumulator().set(ACCUMULATOR.to_managed_address());
+        });
+    world
+}
+
+// Creates `owner` holding one unit of every (collection, nonce, royalties, creator) item
+pub fn set_nfts(
+    world: &mut ScenarioWorld,
+    owner: impl AnnotatedValue<ScenarioTxEnvData, ManagedAddress<StaticApi>>,
+    items: &[(TestTokenIdentifier, u64, u64, TestAddress)],
+) {
+    let mut account = world.account(owner).nonce(1);
+    for (token, nonce, royalties, creator) in items {
+        account = account.esdt_nft_all_properties(
+            *token,
+            *nonce,
+            1,
+            ManagedBuffer::new(),
+            *royalties,
+            Some(*creator),
+            ManagedBuffer::new(),
+            Vec::<ManagedBuffer<StaticApi>>::new(),
+        );
+    }
+}
+
+pub fn nft_payments(items: &[(TestTokenIdentifier, u64)]) -> MultiEsdtPayment<StaticApi> {
+    let mut payments = MultiEsdtPayment::new();
+    for (token, nonce) in items {
+        payments.push(TestEsdtTransfer(*token, *nonce, 1).into());
+    }
+    payments
+}
+
+pub fn set_time(world: &mut ScenarioWorld, timestamp: u64) {
+    world
+        .current_block()
+        .block_timestamp_seconds(TimestampSeconds::new(timestamp));
+}
+
+pub fn royalties_of(world: &mut ScenarioWorld, creator: TestAddress) -> u64 {
+    let mut amount = 0;
+    world
+        .query()
+        .to(ACCUMULATOR)
+        .whitebox(accumulator_mock::contract_obj, |sc| {
+            amount = sc
+                .royalties(&creator.to_managed_address())
+                .get()
+                .to_u64()
+                .unwrap();
+        });
+    amount
+}
+
+pub fn fees_of(world: &mut ScenarioWorld) -> u64 {
+    token_fees_of(world, EgldOrEsdtTokenIdentifier::egld())
+}
+
+pub fn token_fees_of(
+    world: &mut ScenarioWorld,
+    token: EgldOrEsdtTokenIdentifier<StaticApi>,
+) -> u64 {
+    let mut amount = 0;
+    let token = token.into_name().to_vec();
+    world
+        .query()
+        .to(ACCUMULATOR)
+        .whitebox(accumulator_mock::contract_obj, |sc| {
+            let token = EgldOrEsdtTokenIdentifier::parse(ManagedBuffer::new_from_bytes(&token));
+            amount = sc.fees(&token).get().to_u64().unwrap();
+        });
+    amount
+}
+
+pub fn check_nft(
+    world: &mut ScenarioWorld,
+    owner: impl AnnotatedValue<ScenarioTxEnvData, ManagedAddress<StaticApi>>,
+    token: TestTokenIdentifier,
+    nonce: u64,
+) {
+    world.check_account(owner).esdt_nft_balance_and_attributes(
+        token,
+        nonce,
+        1,
+        ManagedBuffer::<StaticApi>::new(),
+    );
+}
+
+pub fn fixed_listing(
+    token: TestTokenIdentifier,
+    nonce: u64,
+    price: u64,
+    payment_token: EgldOrEsdtTokenIdentifier<DebugApi>,
+) -> BulkListing<DebugApi> {
+    BulkListing {
+        min_bid: BigUint::from(price),
+        max_bid: BigUint::from(price),
+        deadline: 0,
+        accepted_payment_token: payment_token,
+        bid: false,
+        opt_sft_max_one_per_payment: false,
+        opt_start_time: 0,
+        collection: EgldOrEsdtTokenIdentifier::esdt(token.as_str()),
+        nonce,
+        nft_amount: BigUint::from(1u64),
+        royalties: BigUint::zero(),
+        reserved_buyer: None,
+        merkle_root: None,
+    }
+}
+
+// Lists every (collection, nonce, price) item of `seller` at a fixed EGLD price, auction ids follow the order
+pub fn list_nfts(
+    world: &mut ScenarioWorld,
+    seller: TestAddress,
+    items: &[(TestTokenIdentifier, u64, u64)],
+) {
+    let payments: Vec<_> = items
+        .iter()
+        .map(|(token, nonce, _)| (*token, *nonce))
+        .collect();
+    world
+        .tx()
+        .from(seller)
+        .to(XOXNO)
+        .multi_esdt(nft_payments(&payments))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            let mut listings = MultiValueEncoded::new();
+            for (token, nonce, price) in items {
+                listings.push(fixed_listing(
+                    *token,
+                    *nonce,
+                    *price,
+                    EgldOrEsdtTokenIdentifier::egld(),
+                ));
+            }
+            sc.listing(listings);
+        });
+}
+
+pub fn buy_call(
+    sc: &xoxno_protocol::ContractObj<DebugApi>,
+    auction_id: u64,
+    token: TestTokenIdentifier,
+    nonce: u64,
+    referrer: Option<TestAddress>,
+) {
+    sc.buy(
+        auction_id,
+        TokenIdentifier::from(token.as_str()),
+        nonce,
+        OptionalValue::None,
+        referrer
+            .map(|referrer| referrer.to_managed_address())
+            .into(),
+        OptionalValue::None,
+    )
+}
+
+// Sends an offer for NFT #`nonce` at `price`, attaching `attached` EGLD as a deposit
+pub fn send_offer(
+    world: &mut ScenarioWorld,
+    buyer: TestAddress,
+    nonce: u64,
+    price: u64,
+    attached: u64,
+) {
+    world
+        .tx()
+        .from(buyer)
+        .to(XOXNO)
+        .egld(attached)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.send_offer(
+                EgldOrEsdtTokenIdentifier::egld(),
+                0,
+                BigUint::from(price),
+                TokenIdentifier::from(NFT.as_str()),
+                nonce,
+                BigUint::from(1u64),
+                1_000,
+            );
+        });
+}
+
+// Returns the (deposited, committed) EGLD of `user`
+pub fn deposit_of(world: &mut ScenarioWorld, user: TestAddress) -> (u64, u64) {
+    let mut result = (0, 0);
+    world
+        .query()
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            let user = user.to_managed_address();
+            let egld = EgldOrEsdtTokenIdentifier::egld();
+            result = (
+                sc.get_balance(&user, &egld, 0).to_u64().unwrap(),
+                sc.committed_funds(&user, &egld, 0).get().to_u64().unwrap(),
+            );
+        });
+    result
+}
+
+pub fn blacklist(world: &mut ScenarioWorld, wallet: TestAddress) {
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.add_blacklist(
+                wallet.to_managed_address(),
+                OptionalValue::None,
+                OptionalValue::None,
+            );
+        });
+}
+
+// Buys `auction_id` for `price` EGLD, expecting `expected_error` when given
+pub fn buy_nft(
+    world: &mut ScenarioWorld,
+    buyer: TestAddress,
+    auction_id: u64,
+    (token, nonce): (TestTokenIdentifier, u64),
+    price: u64,
+    expected_error: Option<&str>,
+) {
+    let tx = world.tx().from(buyer).to(XOXNO).egld(price);
+    let call =
+        |sc: xoxno_protocol::ContractObj<DebugApi>| buy_call(&sc, auction_id, token, nonce, None);
+    match expected_error {
+        Some(message) => tx
+            .returns(ExpectMessage(message))
+            .whitebox(xoxno_protocol::contract_obj, call),
+        None => tx.whitebox(xoxno_protocol::contract_obj, call),
+    }
+}
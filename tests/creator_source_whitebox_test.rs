@@ -0,0 +1,106 @@
+mod common;
+
+use common::*;
+use multiversx_sc_scenario::imports::*;
+use xoxno_protocol::{admin::AdminModule, auction::CreatorSource};
+
+fn set_creator_source(
+    world: &mut ScenarioWorld,
+    from: TestAddress,
+    source: impl FnOnce() -> CreatorSource<DebugApi>,
+    expected_error: Option<&str>,
+) {
+    let tx = world.tx().from(from).to(XOXNO);
+    let call = |sc: xoxno_protocol::ContractObj<DebugApi>| {
+        sc.set_creator_source(NFT.to_token_identifier(), source())
+    };
+    match expected_error {
+        Some(message) => tx
+            .returns(ExpectMessage(message))
+            .whitebox(xoxno_protocol::contract_obj, call),
+        None => tx.whitebox(xoxno_protocol::contract_obj, call),
+    }
+}
+
+fn sell(world: &mut ScenarioWorld, auction_id: u64, nonce: u64) {
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .egld(10_000)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            buy_call(&sc, auction_id, NFT, nonce, None)
+        });
+}
+
+#[test]
+fn creator_from_attributes_test() {
+    let mut world = setup();
+    // The minter is the on-chain creator, the artist address follows a 2 bytes prefix
+    let mut attributes = vec![1u8, 2u8];
+    attributes.extend_from_slice(OTHER_CREATOR.to_address().as_bytes());
+    world.account(SELLER).nonce(1).esdt_nft_all_properties(
+        NFT,
+        1,
+        1,
+        ManagedBuffer::<StaticApi>::new_from_bytes(&attributes),
+        1_000,
+        Some(CREATOR),
+        ManagedBuffer::new(),
+        Vec::<ManagedBuffer<StaticApi>>::new(),
+    );
+    set_creator_source(
+        &mut world,
+        OWNER,
+        || CreatorSource::Attributes { offset: 2 },
+        None,
+    );
+    list_nfts(&mut world, SELLER, &[(NFT, 1, 10_000)]);
+    sell(&mut world, 1, 1);
+
+    assert_eq!(royalties_of(&mut world, OTHER_CREATOR), 1_000);
+    assert_eq!(royalties_of(&mut world, CREATOR), 0);
+}
+
+#[test]
+fn fixed_creator_test() {
+    let mut world = setup();
+    set_creator_source(
+        &mut world,
+        BUYER,
+        || CreatorSource::Fixed(BUYER.to_managed_address()),
+        Some("You are not an admin!"),
+    );
+    set_creator_source(
+        &mut world,
+        OWNER,
+        || CreatorSource::Fixed(ManagedAddress::zero()),
+        Some("Invalid creator address!"),
+    );
+    set_creator_source(
+        &mut world,
+        OWNER,
+        || CreatorSource::Fixed(OTHER_CREATOR.to_managed_address()),
+        None,
+    );
+    set_nfts(
+        &mut world,
+        SELLER,
+        &[(NFT, 1, 1_000, CREATOR), (NFT, 2, 1_000, CREATOR)],
+    );
+    list_nfts(&mut world, SELLER, &[(NFT, 1, 10_000), (NFT, 2, 10_000)]);
+    sell(&mut world, 1, 1);
+    assert_eq!(royalties_of(&mut world, OTHER_CREATOR), 1_000);
+
+    // Without a source the on-chain creator is paid again
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.remove_creator_source(NFT.to_token_identifier())
+        });
+    sell(&mut world, 2, 2);
+    assert_eq!(royalties_of(&mut world, CREATOR), 1_000);
+    assert_eq!(royalties_of(&mut world, OTHER_CREATOR), 1_000);
+}
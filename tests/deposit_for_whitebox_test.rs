@@ -0,0 +1,61 @@
+mod common;
+
+use common::*;
+use multiversx_sc_scenario::imports::*;
+use xoxno_protocol::pools::PoolsModule;
+
+#[test]
+fn deposit_for_test() {
+    let mut world = setup();
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .egld(1_000)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.deposit_for(OTHER_BUYER.to_managed_address())
+        });
+
+    // The beneficiary owns the deposit and can spend or withdraw it
+    assert_eq!(deposit_of(&mut world, BUYER), (0, 0));
+    assert_eq!(deposit_of(&mut world, OTHER_BUYER), (1_000, 0));
+    world
+        .tx()
+        .from(OTHER_BUYER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.withdraw_deposit(
+                &EgldOrEsdtTokenIdentifier::egld(),
+                0,
+                &BigUint::from(1_000u64),
+            )
+        });
+    world.check_account(BUYER).balance(1_000_000 - 1_000);
+    world.check_account(OTHER_BUYER).balance(1_000_000 + 1_000);
+}
+
+#[test]
+fn deposit_for_blacklisted_test() {
+    let mut world = setup();
+    blacklist(&mut world, OTHER_BUYER);
+
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .egld(1_000)
+        .returns(ExpectMessage("The beneficiary address was blacklisted!"))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.deposit_for(OTHER_BUYER.to_managed_address())
+        });
+    world
+        .tx()
+        .from(OTHER_BUYER)
+        .to(XOXNO)
+        .egld(1_000)
+        .returns(ExpectMessage("Your address was blacklisted!"))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.deposit_for(BUYER.to_managed_address())
+        });
+    world.check_account(BUYER).balance(1_000_000);
+}
@@ -0,0 +1,115 @@
+mod common;
+
+use common::*;
+use multiversx_sc_scenario::imports::*;
+use xoxno_protocol::{admin::AdminModule, auction::FeeTier, fees::FeesModule};
+
+fn tier(min_volume: u64, discount: u64) -> FeeTier<DebugApi> {
+    FeeTier {
+        min_volume: BigUint::from(min_volume),
+        discount: BigUint::from(discount),
+    }
+}
+
+fn fee_tier_of(world: &mut ScenarioWorld, user: TestAddress) -> usize {
+    let mut result = 0;
+    world
+        .query()
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            result = sc.get_user_fee_tier(user.to_managed_address()).tier;
+        });
+    result
+}
+
+fn buy(world: &mut ScenarioWorld, auction_id: u64, nonce: u64) {
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .egld(10_000)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            buy_call(&sc, auction_id, NFT, nonce, None)
+        });
+}
+
+#[test]
+fn fee_tier_discount_test() {
+    let mut world = setup();
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.set_fee_tiers(
+                7,
+                MultiValueEncoded::from(ManagedVec::from_single_item(tier(10_000, 5_000))),
+            )
+        });
+    set_nfts(
+        &mut world,
+        SELLER,
+        &[(NFT, 1, 1_000, CREATOR), (NFT, 2, 1_000, CREATOR)],
+    );
+    list_nfts(&mut world, SELLER, &[(NFT, 1, 10_000), (NFT, 2, 10_000)]);
+
+    buy(&mut world, 1, 1);
+    world.check_account(SELLER).balance(8_750);
+    assert_eq!(fee_tier_of(&mut world, SELLER), 1);
+
+    // The first sale's volume halves the cut of the second one
+    buy(&mut world, 2, 2);
+    world.check_account(SELLER).balance(8_750 + 8_875);
+    assert_eq!(fees_of(&mut world), 250 + 125);
+
+    // The volume rolls out of the window
+    world.current_block().block_epoch(7);
+    assert_eq!(fee_tier_of(&mut world, SELLER), 0);
+}
+
+#[test]
+fn fee_tiers_validation_test() {
+    let mut world = setup();
+    let set_tiers = |world: &mut ScenarioWorld,
+                     from: TestAddress,
+                     window_epochs: u64,
+                     tiers: Vec<(u64, u64)>,
+                     message: &str| {
+        world
+            .tx()
+            .from(from)
+            .to(XOXNO)
+            .returns(ExpectMessage(message))
+            .whitebox(xoxno_protocol::contract_obj, |sc| {
+                let mut encoded = MultiValueEncoded::new();
+                for (min_volume, discount) in tiers {
+                    encoded.push(tier(min_volume, discount));
+                }
+                sc.set_fee_tiers(window_epochs, encoded)
+            });
+    };
+
+    set_tiers(&mut world, BUYER, 7, vec![(1, 1)], "You are not an admin!");
+    set_tiers(
+        &mut world,
+        OWNER,
+        7,
+        vec![(10_000, 1_000), (5_000, 2_000)],
+        "Fee tiers must be sorted by ascending volume!",
+    );
+    set_tiers(
+        &mut world,
+        OWNER,
+        7,
+        vec![(10_000, 10_001)],
+        "Fee tier discount cannot exceed 100%!",
+    );
+    set_tiers(
+        &mut world,
+        OWNER,
+        0,
+        vec![(10_000, 1_000)],
+        "Volume window must be higher than 0!",
+    );
+    assert_eq!(fee_tier_of(&mut world, SELLER), 0);
+}
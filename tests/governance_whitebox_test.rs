@@ -0,0 +1,105 @@
+mod common;
+
+use common::*;
+use multiversx_sc_scenario::imports::*;
+use xoxno_protocol::{admin::AdminModule, governance::GovernanceModule, storage::StorageModule};
+
+fn execute_action(world: &mut ScenarioWorld, action_id: u64, expected_error: Option<&str>) {
+    let tx = world.tx().from(BUYER).to(XOXNO);
+    let call = |sc: xoxno_protocol::ContractObj<DebugApi>| sc.execute_action(action_id);
+    match expected_error {
+        Some(message) => tx
+            .returns(ExpectMessage(message))
+            .whitebox(xoxno_protocol::contract_obj, call),
+        None => tx.whitebox(xoxno_protocol::contract_obj, call),
+    }
+}
+
+fn cut_percentage(world: &mut ScenarioWorld) -> u64 {
+    let mut cut = 0;
+    world
+        .query()
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            cut = sc.bid_cut_percentage().get().to_u64().unwrap();
+        });
+    cut
+}
+
+#[test]
+fn timelocked_action_test() {
+    let mut world = setup();
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.set_percentage_cut(500)
+        });
+
+    set_time(&mut world, 86_399);
+    execute_action(&mut world, 1, Some("The action is still timelocked!"));
+    assert_eq!(cut_percentage(&mut world), 250);
+
+    // Anyone executes once the delay elapsed, only once
+    set_time(&mut world, 86_400);
+    execute_action(&mut world, 1, None);
+    assert_eq!(cut_percentage(&mut world), 500);
+    execute_action(&mut world, 1, Some("Action 1 is not queued!"));
+}
+
+#[test]
+fn cancelled_action_test() {
+    let mut world = setup();
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.set_percentage_cut(500);
+            sc.cancel_action(1);
+        });
+    set_time(&mut world, 86_400);
+    execute_action(&mut world, 1, Some("Action 1 is not queued!"));
+    assert_eq!(cut_percentage(&mut world), 250);
+
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .returns(ExpectMessage(
+            "Governance delay must be between 1 hour and 30 days!",
+        ))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.set_governance_delay(3_599)
+        });
+}
+
+#[test]
+fn pause_and_resume_test() {
+    let mut world = setup();
+    let status = |world: &mut ScenarioWorld| {
+        let mut status = false;
+        world
+            .query()
+            .to(XOXNO)
+            .whitebox(xoxno_protocol::contract_obj, |sc| {
+                status = sc.status().get();
+            });
+        status
+    };
+
+    // Pausing is immediate, resuming waits out the timelock
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.set_status(false);
+            sc.set_status(true);
+        });
+    assert!(!status(&mut world));
+    set_time(&mut world, 86_400);
+    execute_action(&mut world, 1, None);
+    assert!(status(&mut world));
+}
@@ -0,0 +1,105 @@
+mod common;
+
+use common::*;
+use multiversx_sc_scenario::imports::*;
+use xoxno_protocol::{admin::AdminModule, auction::Feature, views::ViewsModule};
+
+fn listed_world() -> ScenarioWorld {
+    let mut world = setup();
+    set_nfts(
+        &mut world,
+        SELLER,
+        &[(NFT, 1, 1_000, CREATOR), (OTHER_NFT, 1, 1_000, CREATOR)],
+    );
+    list_nfts(
+        &mut world,
+        SELLER,
+        &[(NFT, 1, 10_000), (OTHER_NFT, 1, 10_000)],
+    );
+    world
+}
+
+#[test]
+fn feature_pause_test() {
+    let mut world = listed_world();
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.set_feature_paused(Feature::Buying, true)
+        });
+    world
+        .query()
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            for (feature, paused) in sc
+                .get_pause_state()
+                .into_iter()
+                .map(|state| state.into_tuple())
+            {
+                assert_eq!(paused, feature == Feature::Buying);
+            }
+        });
+    buy_nft(
+        &mut world,
+        BUYER,
+        1,
+        (NFT, 1),
+        10_000,
+        Some("This feature is currently paused!"),
+    );
+
+    // Other features keep working while buying is paused
+    send_offer(&mut world, BUYER, 1, 1_000, 1_000);
+
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.set_feature_paused(Feature::Buying, false)
+        });
+    buy_nft(&mut world, BUYER, 1, (NFT, 1), 10_000, None);
+    check_nft(&mut world, BUYER, NFT, 1);
+}
+
+#[test]
+fn collection_halt_test() {
+    let mut world = listed_world();
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .returns(ExpectMessage("You are not an admin!"))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.halt_collection(NFT.to_token_identifier())
+        });
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.halt_collection(NFT.to_token_identifier())
+        });
+
+    buy_nft(
+        &mut world,
+        BUYER,
+        1,
+        (NFT, 1),
+        10_000,
+        Some("Trading is halted for this collection!"),
+    );
+    buy_nft(&mut world, BUYER, 2, (OTHER_NFT, 1), 10_000, None);
+
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.resume_collection(NFT.to_token_identifier())
+        });
+    buy_nft(&mut world, BUYER, 1, (NFT, 1), 10_000, None);
+    check_nft(&mut world, BUYER, NFT, 1);
+}
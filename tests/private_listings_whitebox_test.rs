@@ -0,0 +1,78 @@
+mod common;
+
+use common::*;
+use multiversx_sc_scenario::imports::*;
+use xoxno_protocol::XOXNOProtocol;
+
+// Lists NFT #1 of the seller at 10_000 EGLD, only buyable by `reserved`
+fn list_reserved(world: &mut ScenarioWorld, reserved: TestAddress) {
+    set_nfts(world, SELLER, &[(NFT, 1, 1_000, CREATOR)]);
+    world
+        .tx()
+        .from(SELLER)
+        .to(XOXNO)
+        .esdt(TestEsdtTransfer(NFT, 1, 1))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            let mut listing = fixed_listing(NFT, 1, 10_000, EgldOrEsdtTokenIdentifier::egld());
+            listing.reserved_buyer = Some(reserved.to_managed_address());
+            let mut listings = MultiValueEncoded::new();
+            listings.push(listing);
+            sc.listing(listings);
+        });
+}
+
+#[test]
+fn reserved_listing_buy_test() {
+    let mut world = setup();
+    list_reserved(&mut world, BUYER);
+
+    buy_nft(&mut world, BUYER, 1, (NFT, 1), 10_000, None);
+
+    // The private sale still pays the marketplace fee and the royalties
+    check_nft(&mut world, BUYER, NFT, 1);
+    world.check_account(SELLER).balance(8_750);
+    assert_eq!(royalties_of(&mut world, CREATOR), 1_000);
+    assert_eq!(fees_of(&mut world), 250);
+}
+
+#[test]
+fn reserved_listing_other_buyer_test() {
+    let mut world = setup();
+    list_reserved(&mut world, BUYER);
+
+    buy_nft(
+        &mut world,
+        OTHER_BUYER,
+        1,
+        (NFT, 1),
+        10_000,
+        Some("This listing is reserved for another buyer!"),
+    );
+    world
+        .tx()
+        .from(OTHER_BUYER)
+        .to(XOXNO)
+        .egld(10_000)
+        .returns(ExpectMessage("This listing is reserved for another buyer!"))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.bulk_buy(MultiValueEncoded::from(ManagedVec::from_single_item(1u64)));
+        });
+
+    // Buying on behalf of the reserved buyer is the only way around it
+    world
+        .tx()
+        .from(OTHER_BUYER)
+        .to(XOXNO)
+        .egld(10_000)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.buy_for(
+                1,
+                TokenIdentifier::from(NFT.as_str()),
+                1,
+                OptionalValue::None,
+                OptionalValue::Some(BUYER.to_managed_address()),
+                OptionalValue::None,
+            );
+        });
+    check_nft(&mut world, BUYER, NFT, 1);
+}
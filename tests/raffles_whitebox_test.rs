@@ -0,0 +1,158 @@
+mod common;
+
+use common::*;
+use multiversx_sc_scenario::imports::*;
+use xoxno_protocol::{auction::RaffleStatus, raffles::RafflesModule, storage::StorageModule};
+
+fn list_raffle(world: &mut ScenarioWorld, min_tickets: u64) {
+    set_nfts(world, SELLER, &[(NFT, 1, 1_000, CREATOR)]);
+    world
+        .tx()
+        .from(SELLER)
+        .to(XOXNO)
+        .esdt(TestEsdtTransfer(NFT, 1, 1))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.list_raffle(
+                EgldOrEsdtTokenIdentifier::egld(),
+                BigUint::from(1_000u64),
+                10,
+                min_tickets,
+                100,
+            );
+        });
+}
+
+fn buy_tickets(world: &mut ScenarioWorld, buyer: TestAddress, tickets: u64) {
+    world
+        .tx()
+        .from(buyer)
+        .to(XOXNO)
+        .egld(1_000 * tickets)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.buy_tickets(1, tickets)
+        });
+}
+
+fn raffle_status(world: &mut ScenarioWorld) -> (RaffleStatus, Address) {
+    let mut result = (RaffleStatus::Active, Address::zero());
+    world
+        .query()
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            let raffle = sc.raffle_by_id(1).get();
+            result = (raffle.status, raffle.winner.to_address());
+        });
+    result
+}
+
+#[test]
+fn raffle_draw_test() {
+    let mut world = setup();
+    list_raffle(&mut world, 2);
+    buy_tickets(&mut world, BUYER, 2);
+    buy_tickets(&mut world, OTHER_BUYER, 1);
+
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .returns(ExpectMessage("The raffle is still selling tickets!"))
+        .whitebox(xoxno_protocol::contract_obj, |sc| sc.draw_raffle(1));
+
+    set_time(&mut world, 100);
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| sc.draw_raffle(1));
+
+    let (status, winner) = raffle_status(&mut world);
+    assert!(status == RaffleStatus::Drawn);
+    assert!(winner == BUYER.to_address() || winner == OTHER_BUYER.to_address());
+    check_nft(&mut world, &winner, NFT, 1);
+    // 3_000 raised minus 10% royalties and the 2.5% marketplace cut
+    world.check_account(SELLER).balance(2_625);
+    assert_eq!(royalties_of(&mut world, CREATOR), 300);
+    assert_eq!(fees_of(&mut world), 75);
+
+    world
+        .tx()
+        .from(OTHER_BUYER)
+        .to(XOXNO)
+        .returns(ExpectMessage(
+            "The raffle was drawn, tickets can't be refunded!",
+        ))
+        .whitebox(xoxno_protocol::contract_obj, |sc| sc.refund_raffle(1));
+}
+
+#[test]
+fn raffle_refund_test() {
+    let mut world = setup();
+    list_raffle(&mut world, 3);
+    buy_tickets(&mut world, BUYER, 2);
+
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .returns(ExpectMessage("The raffle is still active!"))
+        .whitebox(xoxno_protocol::contract_obj, |sc| sc.refund_raffle(1));
+
+    set_time(&mut world, 100);
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .returns(ExpectMessage(
+            "The minimum tickets were not sold, the raffle can only be refunded!",
+        ))
+        .whitebox(xoxno_protocol::contract_obj, |sc| sc.draw_raffle(1));
+
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| sc.refund_raffle(1));
+    assert!(raffle_status(&mut world).0 == RaffleStatus::Failed);
+    check_nft(&mut world, SELLER, NFT, 1);
+    world.check_account(BUYER).balance(1_000_000);
+
+    // Tickets are refunded once
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| sc.refund_raffle(1));
+    world.check_account(BUYER).balance(1_000_000);
+}
+
+#[test]
+fn raffle_fees_over_limit_refund_test() {
+    let mut world = setup();
+    list_raffle(&mut world, 1);
+    buy_tickets(&mut world, BUYER, 1);
+
+    // A cut raised after the listing leaves the seller under 10%, the draw refunds instead
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.bid_cut_percentage().set(BigUint::from(8_500u64))
+        });
+    set_time(&mut world, 100);
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| sc.draw_raffle(1));
+
+    assert!(raffle_status(&mut world).0 == RaffleStatus::Failed);
+    check_nft(&mut world, SELLER, NFT, 1);
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| sc.refund_raffle(1));
+    world.check_account(BUYER).balance(1_000_000);
+}
@@ -0,0 +1,161 @@
+mod common;
+
+use common::*;
+use multiversx_sc_scenario::imports::*;
+use xoxno_protocol::{admin::AdminModule, fees::FeesModule, XOXNOProtocol};
+
+const REFERRER: TestAddress = TestAddress::new("referrer");
+const XOXNO_TOKEN: TestTokenIdentifier = TestTokenIdentifier::new("XOXNO-abcdef");
+
+// The referrer earns 20% of the 2.5% cut on two 10_000 listings, the buyer can settle
+// the rest of the cut in locked XOXNO at 2 XOXNO per EGLD unit
+fn setup_referrals() -> ScenarioWorld {
+    let mut world = setup();
+    world.account(REFERRER).nonce(1);
+    world.set_esdt_balance(BUYER, b"XOXNO-abcdef", 1_000u64);
+    set_nfts(
+        &mut world,
+        SELLER,
+        &[(NFT, 1, 1_000, CREATOR), (NFT, 2, 1_000, CREATOR)],
+    );
+    list_nfts(&mut world, SELLER, &[(NFT, 1, 10_000), (NFT, 2, 10_000)]);
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.set_referrer(REFERRER.to_managed_address(), 2_000);
+            sc.set_xoxno_token(XOXNO_TOKEN.to_token_identifier());
+            let mut rates = MultiValueEncoded::new();
+            rates.push(
+                (
+                    EgldOrEsdtTokenIdentifier::egld(),
+                    BigUint::from(2_000_000_000_000_000_000u64),
+                )
+                    .into(),
+            );
+            sc.set_xoxno_fee_payment(BigUint::zero(), rates);
+        });
+    world
+}
+
+fn pay_fees_in_xoxno(world: &mut ScenarioWorld) {
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .single_esdt(
+            &XOXNO_TOKEN.to_token_identifier(),
+            0,
+            &BigUint::from(1_000u64),
+        )
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.lock_xoxno();
+            sc.set_pay_fees_in_xoxno(true);
+        });
+}
+
+fn xoxno_locked(world: &mut ScenarioWorld) -> u64 {
+    let mut amount = 0;
+    world
+        .query()
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            amount = sc
+                .xoxno_locked(&BUYER.to_managed_address())
+                .get()
+                .to_u64()
+                .unwrap();
+        });
+    amount
+}
+
+fn xoxno_fees_of(world: &mut ScenarioWorld) -> u64 {
+    token_fees_of(world, EgldOrEsdtTokenIdentifier::esdt(XOXNO_TOKEN.as_str()))
+}
+
+#[test]
+fn referral_with_xoxno_fee_test() {
+    let mut world = setup_referrals();
+    pay_fees_in_xoxno(&mut world);
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .egld(10_000)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            buy_call(&sc, 1, NFT, 1, Some(REFERRER))
+        });
+
+    // The 250 cut pays the 50 referral, the remaining 200 is settled as 400 XOXNO and refunded
+    check_nft(&mut world, BUYER, NFT, 1);
+    world.check_account(BUYER).balance(1_000_000 - 9_800);
+    world.check_account(SELLER).balance(8_750);
+    world.check_account(REFERRER).balance(50);
+    assert_eq!(royalties_of(&mut world, CREATOR), 1_000);
+    assert_eq!(fees_of(&mut world), 0);
+    assert_eq!(xoxno_fees_of(&mut world), 400);
+    assert_eq!(xoxno_locked(&mut world), 600);
+}
+
+#[test]
+fn bulk_referral_with_xoxno_fee_test() {
+    let mut world = setup_referrals();
+    pay_fees_in_xoxno(&mut world);
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .egld(20_000)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.bulk_buy_referred(
+                REFERRER.to_managed_address(),
+                MultiValueEncoded::from(ManagedVec::from(vec![1u64, 2u64])),
+            );
+        });
+
+    check_nft(&mut world, BUYER, NFT, 1);
+    check_nft(&mut world, BUYER, NFT, 2);
+    world.check_account(BUYER).balance(1_000_000 - 19_600);
+    world.check_account(SELLER).balance(17_500);
+    world.check_account(REFERRER).balance(100);
+    assert_eq!(royalties_of(&mut world, CREATOR), 2_000);
+    assert_eq!(fees_of(&mut world), 0);
+    assert_eq!(xoxno_fees_of(&mut world), 800);
+    assert_eq!(xoxno_locked(&mut world), 200);
+}
+
+#[test]
+fn referral_rejected_test() {
+    let mut world = setup_referrals();
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .returns(ExpectMessage("You are not an admin!"))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.set_referrer(BUYER.to_managed_address(), 2_000)
+        });
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .returns(ExpectMessage(
+            "Invalid percentage value, should be between 0 and 10,000",
+        ))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.set_referrer(BUYER.to_managed_address(), 10_001)
+        });
+
+    // Unknown referrers earn nothing, the whole cut goes to the treasury
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .egld(10_000)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            buy_call(&sc, 1, NFT, 1, Some(OTHER_BUYER))
+        });
+    world.check_account(OTHER_BUYER).balance(1_000_000);
+    assert_eq!(fees_of(&mut world), 250);
+}
@@ -0,0 +1,121 @@
+mod common;
+
+use common::*;
+use multiversx_sc_scenario::imports::*;
+use xoxno_protocol::rentals::RentalsModule;
+
+// Lists NFT #1 at 100 per day for up to 10 days with a 5_000 collateral and rents it for 3 days
+fn rent_nft(world: &mut ScenarioWorld) {
+    set_nfts(world, SELLER, &[(NFT, 1, 1_000, CREATOR)]);
+    world
+        .tx()
+        .from(SELLER)
+        .to(XOXNO)
+        .esdt(TestEsdtTransfer(NFT, 1, 1))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.list_rental(
+                EgldOrEsdtTokenIdentifier::egld(),
+                BigUint::from(100u64),
+                10,
+                BigUint::from(5_000u64),
+            );
+        });
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .egld(5_300)
+        .whitebox(xoxno_protocol::contract_obj, |sc| sc.rent(1, 3));
+    check_nft(world, BUYER, NFT, 1);
+}
+
+fn return_rental(world: &mut ScenarioWorld) {
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .esdt(TestEsdtTransfer(NFT, 1, 1))
+        .whitebox(xoxno_protocol::contract_obj, |sc| sc.return_rental(1));
+}
+
+#[test]
+fn rental_return_test() {
+    let mut world = setup();
+    rent_nft(&mut world);
+
+    set_time(&mut world, 86_400);
+    return_rental(&mut world);
+
+    // The collateral is back in full, the 300 rent is split between owner, creator and marketplace
+    world.check_account(BUYER).balance(1_000_000 - 300);
+    world.check_account(SELLER).balance(263);
+    assert_eq!(royalties_of(&mut world, CREATOR), 30);
+    assert_eq!(fees_of(&mut world), 7);
+    check_nft(&mut world, XOXNO, NFT, 1);
+
+    // The NFT is escrowed again and the owner can take it back
+    world
+        .tx()
+        .from(SELLER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| sc.cancel_rental(1));
+    check_nft(&mut world, SELLER, NFT, 1);
+}
+
+#[test]
+fn rental_late_return_test() {
+    let mut world = setup();
+    rent_nft(&mut world);
+
+    // A day and a half late is charged as two extra days out of the collateral
+    set_time(&mut world, 3 * 86_400 + 129_600);
+    return_rental(&mut world);
+
+    world.check_account(BUYER).balance(1_000_000 - 500);
+    world.check_account(SELLER).balance(438);
+    assert_eq!(royalties_of(&mut world, CREATOR), 50);
+    assert_eq!(fees_of(&mut world), 12);
+}
+
+#[test]
+fn rental_forfeit_test() {
+    let mut world = setup();
+    rent_nft(&mut world);
+
+    world
+        .tx()
+        .from(SELLER)
+        .to(XOXNO)
+        .returns(ExpectMessage("The rental period has not expired yet!"))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.claim_rental_collateral(1)
+        });
+
+    set_time(&mut world, 3 * 86_400);
+    world
+        .tx()
+        .from(SELLER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.claim_rental_collateral(1)
+        });
+
+    // The owner keeps the collateral on top of the rent, the renter keeps the NFT
+    world.check_account(SELLER).balance(5_263);
+    world.check_account(BUYER).balance(1_000_000 - 5_300);
+    check_nft(&mut world, BUYER, NFT, 1);
+    world
+        .query()
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            assert!(sc.rental_by_id(1).is_empty());
+        });
+
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .esdt(TestEsdtTransfer(NFT, 1, 1))
+        .returns(ExpectMessage("Rental 1 does not exist!"))
+        .whitebox(xoxno_protocol::contract_obj, |sc| sc.return_rental(1));
+}
@@ -0,0 +1,74 @@
+mod common;
+
+use common::*;
+use multiversx_sc_scenario::imports::*;
+use xoxno_protocol::{
+    admin::AdminModule,
+    auction::{Feature, Role},
+    storage::StorageModule,
+};
+
+const SIGNER: TestAddress = TestAddress::new("signer");
+
+fn call_as(
+    world: &mut ScenarioWorld,
+    from: TestAddress,
+    expected_error: Option<&str>,
+    call: impl FnOnce(xoxno_protocol::ContractObj<DebugApi>),
+) {
+    let tx = world.tx().from(from).to(XOXNO);
+    match expected_error {
+        Some(message) => tx
+            .returns(ExpectMessage(message))
+            .whitebox(xoxno_protocol::contract_obj, call),
+        None => tx.whitebox(xoxno_protocol::contract_obj, call),
+    }
+}
+
+#[test]
+fn signer_role_seeded_on_deploy_test() {
+    let mut world = setup_with_signer(SIGNER);
+    world.account(SIGNER).nonce(1);
+
+    // The signer freezes auctions for swaps but holds no other rights
+    call_as(&mut world, SIGNER, None, |sc| sc.freeze_auction_id(1));
+    world
+        .query()
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            assert!(sc.has_role(Role::Signer, SIGNER.to_managed_address()));
+            assert!(sc.freezed_auctions().contains(&1));
+        });
+    call_as(&mut world, SIGNER, Some("You are not an admin!"), |sc| {
+        sc.set_feature_paused(Feature::Buying, true)
+    });
+}
+
+#[test]
+fn granted_role_test() {
+    let mut world = setup();
+    call_as(&mut world, BUYER, Some("You are not an admin!"), |sc| {
+        sc.set_feature_paused(Feature::Buying, true)
+    });
+
+    call_as(&mut world, OWNER, None, |sc| {
+        sc.grant_role(Role::Pauser, BUYER.to_managed_address())
+    });
+    call_as(&mut world, BUYER, None, |sc| {
+        sc.set_feature_paused(Feature::Buying, true)
+    });
+    // A pauser can't act outside its role
+    call_as(&mut world, BUYER, Some("You are not an admin!"), |sc| {
+        sc.freeze_auction_id(1)
+    });
+
+    call_as(&mut world, OWNER, None, |sc| {
+        sc.revoke_role(Role::Pauser, BUYER.to_managed_address())
+    });
+    call_as(&mut world, BUYER, Some("You are not an admin!"), |sc| {
+        sc.set_feature_paused(Feature::Buying, false)
+    });
+    call_as(&mut world, OWNER, Some("Invalid role address!"), |sc| {
+        sc.grant_role(Role::Pauser, ManagedAddress::zero())
+    });
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use common::*;
+use multiversx_sc_scenario::imports::*;
+use xoxno_protocol::{auction::RoyaltySplit, creator::CreatorModule};
+
+fn set_splits(
+    world: &mut ScenarioWorld,
+    from: TestAddress,
+    splits: &[(TestAddress, u64)],
+    expected_error: Option<&str>,
+) {
+    let tx = world.tx().from(from).to(XOXNO);
+    let call = |sc: xoxno_protocol::ContractObj<DebugApi>| {
+        let mut encoded = MultiValueEncoded::new();
+        for (address, share) in splits {
+            encoded.push(RoyaltySplit {
+                address: address.to_managed_address(),
+                share: BigUint::from(*share),
+            });
+        }
+        sc.set_royalty_splits(&NFT.to_token_identifier(), encoded)
+    };
+    match expected_error {
+        Some(message) => tx
+            .returns(ExpectMessage(message))
+            .whitebox(xoxno_protocol::contract_obj, call),
+        None => tx.whitebox(xoxno_protocol::contract_obj, call),
+    }
+}
+
+#[test]
+fn royalty_splits_test() {
+    let mut world = setup();
+    set_splits(
+        &mut world,
+        OWNER,
+        &[(CREATOR, 7_000), (OTHER_CREATOR, 3_000)],
+        None,
+    );
+    set_nfts(&mut world, SELLER, &[(NFT, 1, 1_000, CREATOR)]);
+    list_nfts(&mut world, SELLER, &[(NFT, 1, 10_010)]);
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .egld(10_010)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            buy_call(&sc, 1, NFT, 1, None)
+        });
+
+    // The last recipient takes the rounding dust of the 1_001 royalties
+    assert_eq!(royalties_of(&mut world, CREATOR), 700);
+    assert_eq!(royalties_of(&mut world, OTHER_CREATOR), 301);
+    world.check_account(SELLER).balance(8_759);
+}
+
+#[test]
+fn royalty_splits_rejected_test() {
+    let mut world = setup();
+    set_splits(
+        &mut world,
+        BUYER,
+        &[(BUYER, 10_000)],
+        Some("You are not an admin!"),
+    );
+    set_splits(
+        &mut world,
+        OWNER,
+        &[(CREATOR, 7_000), (OTHER_CREATOR, 2_000)],
+        Some("Royalty split shares must add up to 10,000!"),
+    );
+    set_splits(
+        &mut world,
+        OWNER,
+        &[(CREATOR, 10_000), (OTHER_CREATOR, 0)],
+        Some("Royalty split share must be higher than 0!"),
+    );
+    let too_many = [(CREATOR, 1_000); 11];
+    set_splits(
+        &mut world,
+        OWNER,
+        &too_many,
+        Some("Cannot split royalties between more than 10 addresses!"),
+    );
+}
@@ -0,0 +1,138 @@
+mod common;
+
+use common::*;
+use ed25519_dalek::{Signer, SigningKey};
+use multiversx_sc::codec::NestedEncode;
+use multiversx_sc_scenario::imports::*;
+use xoxno_protocol::{
+    auction::SignedOffer, offers::CustomOffersModule, orders::OrdersModule, pools::PoolsModule,
+};
+
+fn signed_offer<M: ManagedTypeApi>(buyer: &Address, price: u64) -> SignedOffer<M> {
+    SignedOffer {
+        buyer: ManagedAddress::from_address(buyer),
+        collection: TokenIdentifier::from(NFT.as_str()),
+        token_nonce: 1,
+        quantity: BigUint::from(1u64),
+        payment_token: EgldOrEsdtTokenIdentifier::egld(),
+        payment_nonce: 0,
+        price: BigUint::from(price),
+        deadline: 1_000,
+        salt: 0,
+        counter: 0,
+    }
+}
+
+// Signs the offer the way the contract rebuilds it, prefixed with the contract address
+fn sign_offer(key: &SigningKey, offer: &SignedOffer<StaticApi>) -> Vec<u8> {
+    let mut encoded = ManagedBuffer::<StaticApi>::new();
+    offer.dep_encode(&mut encoded).unwrap();
+    let mut data = XOXNO.to_address().as_bytes().to_vec();
+    data.extend_from_slice(encoded.to_boxed_bytes().as_slice());
+    key.sign(&data).to_bytes().to_vec()
+}
+
+// The buyer is the signing key's address, with 10_000 EGLD deposited
+fn setup_signed_offers(world: &mut ScenarioWorld, key: &SigningKey) -> Address {
+    let buyer = Address::from(key.verifying_key().to_bytes());
+    world.account(&buyer).nonce(1).balance(10_000);
+    set_nfts(world, SELLER, &[(NFT, 1, 1_000, CREATOR)]);
+    world
+        .tx()
+        .from(&buyer)
+        .to(XOXNO)
+        .egld(10_000)
+        .whitebox(xoxno_protocol::contract_obj, |sc| sc.deposit());
+    buyer
+}
+
+fn accept_signed_offer(
+    world: &mut ScenarioWorld,
+    buyer: &Address,
+    signature: Vec<u8>,
+    expected_error: Option<&str>,
+) {
+    let tx = world
+        .tx()
+        .from(SELLER)
+        .to(XOXNO)
+        .esdt(TestEsdtTransfer(NFT, 1, 1));
+    let call = |sc: xoxno_protocol::ContractObj<DebugApi>| {
+        sc.accept_signed_offer(
+            signed_offer(buyer, 10_000),
+            ManagedBuffer::new_from_bytes(&signature),
+        )
+    };
+    match expected_error {
+        Some(message) => tx
+            .returns(ExpectMessage(message))
+            .whitebox(xoxno_protocol::contract_obj, call),
+        None => tx.whitebox(xoxno_protocol::contract_obj, call),
+    }
+}
+
+fn buyer_deposit(world: &mut ScenarioWorld, buyer: &Address) -> (u64, u64) {
+    let mut result = (0, 0);
+    world
+        .query()
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            let buyer = ManagedAddress::from_address(buyer);
+            let egld = EgldOrEsdtTokenIdentifier::egld();
+            result = (
+                sc.get_balance(&buyer, &egld, 0).to_u64().unwrap(),
+                sc.committed_funds(&buyer, &egld, 0).get().to_u64().unwrap(),
+            );
+        });
+    result
+}
+
+#[test]
+fn signed_offer_accept_test() {
+    let mut world = setup();
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    let buyer = setup_signed_offers(&mut world, &key);
+
+    let signature = sign_offer(&key, &signed_offer(&buyer, 10_000));
+    accept_signed_offer(&mut world, &buyer, signature, None);
+
+    check_nft(&mut world, &buyer, NFT, 1);
+    world.check_account(SELLER).balance(8_750);
+    assert_eq!(royalties_of(&mut world, CREATOR), 1_000);
+    assert_eq!(buyer_deposit(&mut world, &buyer), (0, 0));
+}
+
+#[test]
+fn signed_offer_committed_funds_test() {
+    let mut world = setup();
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    let buyer = setup_signed_offers(&mut world, &key);
+
+    // The whole deposit backs an on-chain offer, so none of it is free
+    world
+        .tx()
+        .from(&buyer)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.send_offer(
+                EgldOrEsdtTokenIdentifier::egld(),
+                0,
+                BigUint::from(10_000u64),
+                TokenIdentifier::from(NFT.as_str()),
+                2,
+                BigUint::from(1u64),
+                1_000,
+            );
+        });
+
+    let signature = sign_offer(&key, &signed_offer(&buyer, 10_000));
+    accept_signed_offer(
+        &mut world,
+        &buyer,
+        signature,
+        Some("Your free balance is under the requested amount!"),
+    );
+
+    check_nft(&mut world, SELLER, NFT, 1);
+    assert_eq!(buyer_deposit(&mut world, &buyer), (10_000, 10_000));
+}
@@ -0,0 +1,174 @@
+mod common;
+
+use common::*;
+use ed25519_dalek::{Signer, SigningKey};
+use multiversx_sc::codec::NestedEncode;
+use multiversx_sc_scenario::imports::*;
+use xoxno_protocol::{auction::SignedOrder, orders::OrdersModule};
+
+fn signed_order<M: ManagedTypeApi>(
+    seller: &Address,
+    nonce: u64,
+    order_nonce: u64,
+    price: u64,
+) -> SignedOrder<M> {
+    SignedOrder {
+        seller: ManagedAddress::from_address(seller),
+        collection: TokenIdentifier::from(NFT.as_str()),
+        token_nonce: nonce,
+        quantity: BigUint::from(1u64),
+        payment_token: EgldOrEsdtTokenIdentifier::egld(),
+        payment_nonce: 0,
+        price: BigUint::from(price),
+        deadline: 1_000,
+        order_nonce,
+    }
+}
+
+// Signs the order the way the contract rebuilds it, prefixed with the contract address
+fn sign_order(key: &SigningKey, order: &SignedOrder<StaticApi>) -> Vec<u8> {
+    let mut encoded = ManagedBuffer::<StaticApi>::new();
+    order.dep_encode(&mut encoded).unwrap();
+    let mut data = XOXNO.to_address().as_bytes().to_vec();
+    data.extend_from_slice(encoded.to_boxed_bytes().as_slice());
+    key.sign(&data).to_bytes().to_vec()
+}
+
+fn setup_signed_orders(world: &mut ScenarioWorld, key: &SigningKey) -> Address {
+    let seller = Address::from(key.verifying_key().to_bytes());
+    set_nfts(
+        world,
+        &seller,
+        &[(NFT, 1, 1_000, CREATOR), (NFT, 2, 1_000, CREATOR)],
+    );
+    world
+        .tx()
+        .from(&seller)
+        .to(XOXNO)
+        .multi_esdt(nft_payments(&[(NFT, 1), (NFT, 2)]))
+        .whitebox(xoxno_protocol::contract_obj, |sc| sc.escrow_nfts());
+    seller
+}
+
+// Submits the order for `nonce` and `order_nonce` at the 10_000 price with the given signature
+fn buy_signed_order(
+    world: &mut ScenarioWorld,
+    seller: &Address,
+    nonce: u64,
+    order_nonce: u64,
+    signature: Vec<u8>,
+    expected_error: Option<&str>,
+) {
+    let tx = world.tx().from(BUYER).to(XOXNO).egld(10_000);
+    let call = |sc: xoxno_protocol::ContractObj<DebugApi>| {
+        sc.buy_signed_order(
+            signed_order(seller, nonce, order_nonce, 10_000),
+            ManagedBuffer::new_from_bytes(&signature),
+        )
+    };
+    match expected_error {
+        Some(message) => tx
+            .returns(ExpectMessage(message))
+            .whitebox(xoxno_protocol::contract_obj, call),
+        None => tx.whitebox(xoxno_protocol::contract_obj, call),
+    }
+}
+
+#[test]
+fn signed_order_fill_test() {
+    let mut world = setup();
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    let seller = setup_signed_orders(&mut world, &key);
+
+    let signature = sign_order(&key, &signed_order(&seller, 1, 1, 10_000));
+    buy_signed_order(&mut world, &seller, 1, 1, signature.clone(), None);
+
+    check_nft(&mut world, BUYER, NFT, 1);
+    world.check_account(&seller).balance(8_750);
+    assert_eq!(royalties_of(&mut world, CREATOR), 1_000);
+    assert_eq!(fees_of(&mut world), 250);
+
+    // The order nonce is spent, the same signature can't fill it twice
+    buy_signed_order(
+        &mut world,
+        &seller,
+        1,
+        1,
+        signature,
+        Some("The order was cancelled or already filled!"),
+    );
+}
+
+#[test]
+fn signed_order_wrong_signer_test() {
+    let mut world = setup();
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    let seller = setup_signed_orders(&mut world, &key);
+
+    let order = signed_order(&seller, 1, 1, 10_000);
+    let forged = sign_order(&SigningKey::from_bytes(&[8u8; 32]), &order);
+    buy_signed_order(
+        &mut world,
+        &seller,
+        1,
+        1,
+        forged,
+        Some("ed25519 verify error"),
+    );
+
+    // A valid signature over other terms doesn't cover the submitted order
+    let cheaper = sign_order(&key, &signed_order(&seller, 1, 1, 1));
+    buy_signed_order(
+        &mut world,
+        &seller,
+        1,
+        1,
+        cheaper,
+        Some("ed25519 verify error"),
+    );
+}
+
+#[test]
+fn signed_order_nonces_test() {
+    let mut world = setup();
+    let key = SigningKey::from_bytes(&[7u8; 32]);
+    let seller = setup_signed_orders(&mut world, &key);
+
+    world
+        .tx()
+        .from(&seller)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.cancel_signed_orders(MultiValueEncoded::from(ManagedVec::from_single_item(4u64)))
+        });
+    let cancelled = sign_order(&key, &signed_order(&seller, 1, 4, 10_000));
+    buy_signed_order(
+        &mut world,
+        &seller,
+        1,
+        4,
+        cancelled,
+        Some("The order was cancelled or already filled!"),
+    );
+
+    world
+        .tx()
+        .from(&seller)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.invalidate_signed_orders(10)
+        });
+    let stale = sign_order(&key, &signed_order(&seller, 2, 9, 10_000));
+    buy_signed_order(
+        &mut world,
+        &seller,
+        2,
+        9,
+        stale,
+        Some("The order was cancelled or already filled!"),
+    );
+
+    let fresh = sign_order(&key, &signed_order(&seller, 2, 10, 10_000));
+    buy_signed_order(&mut world, &seller, 2, 10, fresh, None);
+    check_nft(&mut world, BUYER, NFT, 2);
+}
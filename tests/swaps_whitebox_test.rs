@@ -0,0 +1,168 @@
+mod common;
+
+use common::*;
+use multiversx_sc_scenario::imports::*;
+use xoxno_protocol::{auction::SwapRequest, nft_swaps::NftSwapsModule, pools::PoolsModule};
+
+// The seller makes swaps for the buyer's OTHER #1, NFT carries a 100 and OTHER a 200 swap royalty
+fn setup_swaps() -> ScenarioWorld {
+    let mut world = setup();
+    set_nfts(&mut world, SELLER, &[(NFT, 1, 1_000, CREATOR)]);
+    world.set_egld_balance(SELLER, 10_000);
+    world.set_nft_balance_all_properties(
+        BUYER,
+        b"OTHER-123456",
+        1,
+        1,
+        ManagedBuffer::<StaticApi>::new(),
+        500,
+        Some(OTHER_CREATOR),
+        None,
+        None,
+        &[],
+    );
+    for (collection, royalty) in [(NFT, 100u64), (OTHER_NFT, 200)] {
+        world
+            .tx()
+            .from(OWNER)
+            .to(XOXNO)
+            .whitebox(xoxno_protocol::contract_obj, |sc| {
+                sc.set_swap_royalty(
+                    TokenIdentifier::from(collection.as_str()),
+                    BigUint::from(royalty),
+                );
+            });
+    }
+    world
+}
+
+fn deposit(world: &mut ScenarioWorld, user: TestAddress, amount: u64) {
+    world
+        .tx()
+        .from(user)
+        .to(XOXNO)
+        .egld(amount)
+        .whitebox(xoxno_protocol::contract_obj, |sc| sc.deposit());
+}
+
+fn create_swap_call(sc: &xoxno_protocol::ContractObj<DebugApi>) {
+    let mut requested = MultiValueEncoded::new();
+    requested.push(SwapRequest {
+        collection: TokenIdentifier::from(OTHER_NFT.as_str()),
+        nonce: 1,
+        amount: BigUint::from(1u64),
+    });
+    sc.create_swap(0, requested);
+}
+
+// Offers NFT #1 for OTHER #1, adding `sweetener` EGLD when it isn't 0
+fn create_swap(world: &mut ScenarioWorld, sweetener: u64) {
+    let mut payments = MultiEgldOrEsdtPayment::<StaticApi>::new();
+    payments.push(EsdtTokenPayment::from(TestEsdtTransfer(NFT, 1, 1)).into());
+    if sweetener > 0 {
+        payments.push(EgldOrEsdtTokenPayment::egld_payment(BigUint::from(
+            sweetener,
+        )));
+    }
+    world
+        .tx()
+        .from(SELLER)
+        .to(XOXNO)
+        .payment(payments)
+        .whitebox(xoxno_protocol::contract_obj, |sc| create_swap_call(&sc));
+}
+
+fn fill_swap(world: &mut ScenarioWorld, taker: TestAddress, expected_error: Option<&str>) {
+    let tx = world
+        .tx()
+        .from(taker)
+        .to(XOXNO)
+        .esdt(TestEsdtTransfer(OTHER_NFT, 1, 1));
+    let call = |sc: xoxno_protocol::ContractObj<DebugApi>| sc.fill_swap(1);
+    match expected_error {
+        Some(message) => tx
+            .returns(ExpectMessage(message))
+            .whitebox(xoxno_protocol::contract_obj, call),
+        None => tx.whitebox(xoxno_protocol::contract_obj, call),
+    }
+}
+
+#[test]
+fn swap_sweetener_royalties_test() {
+    let mut world = setup_swaps();
+    deposit(&mut world, SELLER, 300);
+    deposit(&mut world, BUYER, 100);
+
+    // The maker's swap royalty is set aside from the deposit at creation
+    create_swap(&mut world, 1_000);
+    assert_eq!(deposit_of(&mut world, SELLER), (100, 0));
+
+    fill_swap(&mut world, BUYER, None);
+
+    check_nft(&mut world, BUYER, NFT, 1);
+    check_nft(&mut world, SELLER, OTHER_NFT, 1);
+    // 5% of the sweetener plus the flat swap royalty, the sweetener doesn't replace it
+    assert_eq!(royalties_of(&mut world, OTHER_CREATOR), 250);
+    assert_eq!(royalties_of(&mut world, CREATOR), 100);
+    assert_eq!(fees_of(&mut world), 25);
+    assert_eq!(deposit_of(&mut world, BUYER), (0, 0));
+    world.check_account(BUYER).balance(1_000_000 - 100 + 925);
+}
+
+#[test]
+fn swap_royalty_reserve_test() {
+    let mut world = setup_swaps();
+    deposit(&mut world, SELLER, 200);
+    deposit(&mut world, BUYER, 100);
+    create_swap(&mut world, 0);
+    assert_eq!(deposit_of(&mut world, SELLER), (0, 0));
+
+    // A lowered royalty is paid from the reserve and the rest goes back to the maker's deposit
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.set_swap_royalty(
+                TokenIdentifier::from(OTHER_NFT.as_str()),
+                BigUint::from(150u64),
+            );
+        });
+    fill_swap(&mut world, BUYER, None);
+
+    check_nft(&mut world, SELLER, OTHER_NFT, 1);
+    assert_eq!(royalties_of(&mut world, OTHER_CREATOR), 150);
+    assert_eq!(deposit_of(&mut world, SELLER), (50, 0));
+}
+
+#[test]
+fn swap_rejection_test() {
+    let mut world = setup_swaps();
+    deposit(&mut world, SELLER, 100);
+    deposit(&mut world, BUYER, 100);
+
+    // The reserve can't be covered by the maker's deposit
+    world
+        .tx()
+        .from(SELLER)
+        .to(XOXNO)
+        .esdt(TestEsdtTransfer(NFT, 1, 1))
+        .returns(ExpectMessage(
+            "Your free balance is under the requested amount!",
+        ))
+        .whitebox(xoxno_protocol::contract_obj, |sc| create_swap_call(&sc));
+
+    deposit(&mut world, SELLER, 100);
+    create_swap(&mut world, 0);
+    blacklist(&mut world, BUYER);
+    fill_swap(&mut world, BUYER, Some("Your address was blacklisted!"));
+
+    // Cancelling returns the NFT and the reserve
+    world
+        .tx()
+        .from(SELLER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| sc.cancel_swap(1));
+    check_nft(&mut world, SELLER, NFT, 1);
+    assert_eq!(deposit_of(&mut world, SELLER), (200, 0));
+}
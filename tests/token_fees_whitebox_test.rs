@@ -0,0 +1,83 @@
+mod common;
+
+use common::*;
+use multiversx_sc_scenario::imports::*;
+use xoxno_protocol::{
+    admin::AdminModule, auction::GovernanceAction, fees::FeesModule, governance::GovernanceModule,
+};
+
+fn effective_fee(world: &mut ScenarioWorld) -> u64 {
+    let mut fee = 0;
+    world
+        .query()
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            fee = sc
+                .get_effective_fee(EgldOrEsdtTokenIdentifier::egld())
+                .to_u64()
+                .unwrap();
+        });
+    fee
+}
+
+#[test]
+fn token_cut_override_test() {
+    let mut world = setup();
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.set_token_cut_percentage(EgldOrEsdtTokenIdentifier::egld(), 500)
+        });
+    assert_eq!(effective_fee(&mut world), 250);
+
+    set_time(&mut world, 86_400);
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| sc.execute_action(1));
+    assert_eq!(effective_fee(&mut world), 500);
+
+    // Listings in the token take its cut
+    set_nfts(&mut world, SELLER, &[(NFT, 1, 1_000, CREATOR)]);
+    list_nfts(&mut world, SELLER, &[(NFT, 1, 10_000)]);
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .egld(10_000)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            buy_call(&sc, 1, NFT, 1, None)
+        });
+    world.check_account(SELLER).balance(8_500);
+    assert_eq!(fees_of(&mut world), 500);
+}
+
+#[test]
+fn token_cut_override_rejected_test() {
+    let mut world = setup();
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .returns(ExpectMessage("Endpoint can only be called by owner"))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.propose_action(GovernanceAction::SetTokenCutPercentage(
+                EgldOrEsdtTokenIdentifier::egld(),
+                500,
+            ));
+        });
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .returns(ExpectMessage(
+            "Invalid percentage value, should be between 0 and 10,000",
+        ))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.set_token_cut_percentage(EgldOrEsdtTokenIdentifier::egld(), 10_000)
+        });
+    assert_eq!(effective_fee(&mut world), 250);
+}
@@ -0,0 +1,84 @@
+mod common;
+
+use common::*;
+use multiversx_sc_scenario::imports::*;
+use xoxno_protocol::{offers::CustomOffersModule, pools::PoolsModule, storage::StorageModule};
+
+fn prune(world: &mut ScenarioWorld, offer_ids: &[u64], expected_error: Option<&str>) {
+    let tx = world.tx().from(OTHER_BUYER).to(XOXNO);
+    let call = |sc: xoxno_protocol::ContractObj<DebugApi>| {
+        sc.prune_unfunded_offers(offer_ids.iter().copied().collect())
+    };
+    match expected_error {
+        Some(message) => tx
+            .returns(ExpectMessage(message))
+            .whitebox(xoxno_protocol::contract_obj, call),
+        None => tx.whitebox(xoxno_protocol::contract_obj, call),
+    }
+}
+
+fn unfunded_offers(world: &mut ScenarioWorld, from: u64) -> Vec<u64> {
+    let mut offer_ids = Vec::new();
+    world
+        .query()
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            offer_ids = sc.get_unfunded_offers(from, 10).into_iter().collect();
+        });
+    offer_ids
+}
+
+// Offers 2 and 4 ask for more than the 1_000 left once half the deposit is withdrawn
+fn send_offers(world: &mut ScenarioWorld) {
+    send_offer(world, BUYER, 1, 500, 2_000);
+    send_offer(world, BUYER, 2, 2_000, 0);
+    send_offer(world, BUYER, 3, 500, 0);
+    send_offer(world, BUYER, 4, 2_000, 0);
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.withdraw_deposit(
+                &EgldOrEsdtTokenIdentifier::egld(),
+                0,
+                &BigUint::from(1_000u64),
+            )
+        });
+}
+
+#[test]
+fn prune_unfunded_offers_test() {
+    let mut world = setup();
+    send_offers(&mut world);
+    assert_eq!(unfunded_offers(&mut world, 0), vec![2, 4]);
+
+    // Anyone can prune, funded offers are left alone
+    prune(&mut world, &[1, 2], None);
+    world
+        .query()
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            assert!(!sc.offer_by_id(1).is_empty());
+            assert!(sc.offer_by_id(2).is_empty());
+        });
+    assert_eq!(deposit_of(&mut world, BUYER), (1_000, 3_000));
+
+    // A cursor on a pruned offer resumes at the next live one
+    assert_eq!(unfunded_offers(&mut world, 2), vec![4]);
+    assert_eq!(unfunded_offers(&mut world, 5), Vec::<u64>::new());
+}
+
+#[test]
+fn prune_too_many_offers_test() {
+    let mut world = setup();
+    send_offers(&mut world);
+
+    let offer_ids: Vec<u64> = (1..=81).collect();
+    prune(
+        &mut world,
+        &offer_ids,
+        Some("Cannot prune more than 80 offers at once!"),
+    );
+    assert_eq!(unfunded_offers(&mut world, 0), vec![2, 4]);
+}
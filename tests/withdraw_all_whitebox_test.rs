@@ -0,0 +1,149 @@
+mod common;
+
+use common::*;
+use multiversx_sc_scenario::imports::*;
+use xoxno_protocol::{
+    admin::AdminModule, auction::OffersCursor, offers::CustomOffersModule, pools::PoolsModule,
+    storage::StorageModule, views::ViewsModule, XOXNOProtocol,
+};
+
+// Places `offers` offers on NFT #1.. and `global_offers` global offers of 10 EGLD each from the deposit
+fn place_offers(world: &mut ScenarioWorld, offers: u64, global_offers: u64) {
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .egld(10_000)
+        .whitebox(xoxno_protocol::contract_obj, |sc| sc.deposit());
+    for nonce in 1..=offers {
+        send_offer(world, BUYER, nonce, 10, 0);
+    }
+    for _ in 0..global_offers {
+        world
+            .tx()
+            .from(BUYER)
+            .to(XOXNO)
+            .whitebox(xoxno_protocol::contract_obj, |sc| {
+                sc.send_global_offer(
+                    EgldOrEsdtTokenIdentifier::egld(),
+                    0,
+                    BigUint::from(10u64),
+                    TokenIdentifier::from(NFT.as_str()),
+                    BigUint::from(1u64),
+                    OptionalValue::None,
+                );
+            });
+    }
+}
+
+fn withdraw_all_offers(
+    world: &mut ScenarioWorld,
+    from: TestAddress,
+    cursor: Option<OffersCursor>,
+) -> OffersCursor {
+    let mut next = OffersCursor::Done;
+    world
+        .tx()
+        .from(from)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            next = sc.withdraw_all_offers(cursor.into());
+        });
+    next
+}
+
+fn offer_counts(world: &mut ScenarioWorld) -> (usize, usize) {
+    let mut counts = (0, 0);
+    world
+        .query()
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            counts = (sc.get_offers_count(), sc.get_global_offers_count());
+        });
+    counts
+}
+
+#[test]
+fn withdraw_all_offers_paged_test() {
+    let mut world = setup();
+    place_offers(&mut world, 70, 20);
+    assert_eq!(deposit_of(&mut world, BUYER), (10_000, 900));
+
+    // The first page takes the 70 offers and 10 of the global offers
+    let cursor = withdraw_all_offers(&mut world, BUYER, None);
+    assert!(matches!(cursor, OffersCursor::GlobalOffers(_)));
+    assert_eq!(offer_counts(&mut world), (0, 10));
+
+    assert_eq!(
+        withdraw_all_offers(&mut world, BUYER, Some(cursor)),
+        OffersCursor::Done
+    );
+    assert_eq!(offer_counts(&mut world), (0, 0));
+    assert_eq!(deposit_of(&mut world, BUYER), (10_000, 0));
+}
+
+#[test]
+fn withdraw_all_offers_cursor_test() {
+    let mut world = setup();
+    place_offers(&mut world, 85, 0);
+
+    let cursor = withdraw_all_offers(&mut world, BUYER, None);
+    assert_eq!(cursor, OffersCursor::Offers(81));
+    assert_eq!(offer_counts(&mut world), (5, 0));
+
+    // Another wallet's cursor only ever walks the caller's own offers
+    assert_eq!(
+        withdraw_all_offers(&mut world, OTHER_BUYER, Some(cursor)),
+        OffersCursor::Done
+    );
+    assert_eq!(offer_counts(&mut world), (5, 0));
+
+    assert_eq!(
+        withdraw_all_offers(&mut world, BUYER, Some(cursor)),
+        OffersCursor::Done
+    );
+    assert_eq!(offer_counts(&mut world), (0, 0));
+}
+
+#[test]
+fn withdraw_all_listings_test() {
+    let mut world = setup();
+    set_nfts(
+        &mut world,
+        SELLER,
+        &[(NFT, 1, 1_000, CREATOR), (NFT, 2, 1_000, CREATOR)],
+    );
+    list_nfts(&mut world, SELLER, &[(NFT, 1, 10_000), (NFT, 2, 10_000)]);
+
+    // A paused marketplace keeps the listings where they are
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| sc.set_status(false));
+    world
+        .tx()
+        .from(SELLER)
+        .to(XOXNO)
+        .returns(ExpectMessage("Global operation enabled!"))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.withdraw_all_listings(OptionalValue::None);
+        });
+
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| sc.status().set(true));
+    let mut next = 1;
+    world
+        .tx()
+        .from(SELLER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            next = sc.withdraw_all_listings(OptionalValue::None);
+        });
+    assert_eq!(next, 0);
+    check_nft(&mut world, SELLER, NFT, 1);
+    check_nft(&mut world, SELLER, NFT, 2);
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use common::*;
+use multiversx_sc_scenario::imports::*;
+use xoxno_protocol::{admin::AdminModule, fees::FeesModule};
+
+const XOXNO_TOKEN: TestTokenIdentifier = TestTokenIdentifier::new("XOXNO-abcdef");
+
+// Holding 1_000 XOXNO halves the cut, the seller holds NFT #1 and 1_000 XOXNO in the wallet
+fn setup_discount() -> ScenarioWorld {
+    let mut world = setup();
+    world
+        .account(SELLER)
+        .nonce(1)
+        .esdt_balance(XOXNO_TOKEN, 1_000)
+        .esdt_nft_all_properties(
+            NFT,
+            1,
+            1,
+            ManagedBuffer::new(),
+            1_000,
+            Some(CREATOR),
+            ManagedBuffer::new(),
+            Vec::<ManagedBuffer<StaticApi>>::new(),
+        );
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.set_xoxno_token(XOXNO_TOKEN.to_token_identifier());
+            sc.set_xoxno_holder_discount(BigUint::from(1_000u64), BigUint::from(5_000u64));
+            sc.set_xoxno_lock_period(100);
+        });
+    list_nfts(&mut world, SELLER, &[(NFT, 1, 10_000)]);
+    world
+}
+
+fn lock_xoxno(world: &mut ScenarioWorld) {
+    world
+        .tx()
+        .from(SELLER)
+        .to(XOXNO)
+        .single_esdt(
+            &XOXNO_TOKEN.to_token_identifier(),
+            0,
+            &BigUint::from(1_000u64),
+        )
+        .whitebox(xoxno_protocol::contract_obj, |sc| sc.lock_xoxno());
+}
+
+fn buy(world: &mut ScenarioWorld) {
+    world
+        .tx()
+        .from(BUYER)
+        .to(XOXNO)
+        .egld(10_000)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            buy_call(&sc, 1, NFT, 1, None)
+        });
+}
+
+fn unlock_xoxno(world: &mut ScenarioWorld, expected_error: Option<&str>) {
+    let tx = world.tx().from(SELLER).to(XOXNO);
+    let call = |sc: xoxno_protocol::ContractObj<DebugApi>| sc.unlock_xoxno(BigUint::from(1_000u64));
+    match expected_error {
+        Some(message) => tx
+            .returns(ExpectMessage(message))
+            .whitebox(xoxno_protocol::contract_obj, call),
+        None => tx.whitebox(xoxno_protocol::contract_obj, call),
+    }
+}
+
+#[test]
+fn locked_xoxno_discount_test() {
+    let mut world = setup_discount();
+    lock_xoxno(&mut world);
+    buy(&mut world);
+
+    world.check_account(SELLER).balance(8_875);
+    assert_eq!(fees_of(&mut world), 125);
+
+    set_time(&mut world, 100);
+    unlock_xoxno(&mut world, None);
+    world.check_account(SELLER).esdt_balance(XOXNO_TOKEN, 1_000);
+}
+
+#[test]
+fn wallet_xoxno_no_discount_test() {
+    let mut world = setup_discount();
+
+    // XOXNO only held in the wallet doesn't count towards the discount
+    buy(&mut world);
+    world.check_account(SELLER).balance(8_750);
+    assert_eq!(fees_of(&mut world), 250);
+
+    lock_xoxno(&mut world);
+    unlock_xoxno(&mut world, Some("Your XOXNO is still locked!"));
+}
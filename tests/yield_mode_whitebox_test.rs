@@ -0,0 +1,159 @@
+mod common;
+
+use common::*;
+use multiversx_sc_scenario::imports::*;
+use xoxno_protocol::{
+    admin::AdminModule, offers::CustomOffersModule, pools::PoolsModule, staking::StakingModule,
+};
+
+// Stands in for liquid staking, delegating at a 1:1 rate out of its own token balance
+mod liquid_staking_mock {
+    multiversx_sc::imports!();
+
+    #[multiversx_sc::contract]
+    pub trait LiquidStakingMock {
+        #[init]
+        fn init(&self) {}
+
+        #[payable("EGLD")]
+        #[endpoint(delegate)]
+        fn delegate(&self) {
+            let amount = self.call_value().egld().clone_value();
+            self.tx()
+                .to(&self.blockchain().get_caller())
+                .single_esdt(&TokenIdentifier::from("LXOXNO-abcdef"), 0, &amount)
+                .transfer();
+        }
+
+        #[view(getEgldPositionValue)]
+        fn get_egld_position_value(&self, amount: BigUint) -> BigUint {
+            amount
+        }
+    }
+}
+
+const LIQUID_STAKING_CODE_PATH: MxscPath = MxscPath::new("output/liquid-staking-mock.mxsc.json");
+const LIQUID_STAKING: TestSCAddress = TestSCAddress::new("liquid-staking");
+const LXOXNO: TestTokenIdentifier = TestTokenIdentifier::new("LXOXNO-abcdef");
+
+// Buyers stake their deposits against a 1_000 EGLD buffer, the seller holds NFT #1
+fn setup_yield() -> ScenarioWorld {
+    let mut world = setup();
+    world.register_contract(
+        LIQUID_STAKING_CODE_PATH,
+        liquid_staking_mock::ContractBuilder,
+    );
+    world
+        .account(LIQUID_STAKING)
+        .nonce(1)
+        .code(LIQUID_STAKING_CODE_PATH)
+        .owner(OWNER)
+        .esdt_balance(LXOXNO, 1_000_000);
+    set_nfts(&mut world, SELLER, &[(NFT, 1, 1_000, CREATOR)]);
+    world.set_egld_balance(OWNER, 1_000);
+    world
+        .tx()
+        .from(OWNER)
+        .to(XOXNO)
+        .egld(1_000)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.set_liquid_staking(
+                LIQUID_STAKING.to_managed_address(),
+                LXOXNO.to_token_identifier(),
+            );
+            sc.fund_yield_buffer();
+        });
+    for buyer in [BUYER, OTHER_BUYER] {
+        world
+            .tx()
+            .from(buyer)
+            .to(XOXNO)
+            .egld(2_000)
+            .whitebox(xoxno_protocol::contract_obj, |sc| {
+                sc.set_yield_mode(true);
+                sc.deposit();
+            });
+    }
+    world
+}
+
+fn reserved_buffer(world: &mut ScenarioWorld) -> (u64, u64) {
+    let mut result = (0, 0);
+    world
+        .query()
+        .to(XOXNO)
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            result = (
+                sc.yield_buffer().get().to_u64().unwrap(),
+                sc.yield_buffer_reserved().get().to_u64().unwrap(),
+            );
+        });
+    result
+}
+
+#[test]
+fn yield_offer_payout_test() {
+    let mut world = setup_yield();
+    send_offer(&mut world, BUYER, 1, 800, 0);
+    assert_eq!(reserved_buffer(&mut world), (1_000, 800));
+
+    world
+        .tx()
+        .from(SELLER)
+        .to(XOXNO)
+        .single_esdt(&NFT.to_token_identifier(), 1, &BigUint::from(1u64))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.accept_offer(1, OptionalValue::None, OptionalValue::None)
+        });
+
+    // The staked offer is paid out of the buffer it had reserved
+    check_nft(&mut world, BUYER, NFT, 1);
+    world.check_account(SELLER).balance(700);
+    assert_eq!(royalties_of(&mut world, CREATOR), 80);
+    assert_eq!(reserved_buffer(&mut world), (200, 0));
+    assert_eq!(deposit_of(&mut world, BUYER), (1_200, 0));
+}
+
+#[test]
+fn yield_offer_over_buffer_test() {
+    let mut world = setup_yield();
+    send_offer(&mut world, BUYER, 1, 800, 0);
+
+    // Only 200 of the buffer is left unreserved to back another staked offer
+    world
+        .tx()
+        .from(OTHER_BUYER)
+        .to(XOXNO)
+        .returns(ExpectMessage(
+            "Not enough liquid EGLD to back offers from staked funds, try again later!",
+        ))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.send_offer(
+                EgldOrEsdtTokenIdentifier::egld(),
+                0,
+                BigUint::from(300u64),
+                TokenIdentifier::from(NFT.as_str()),
+                1,
+                BigUint::from(1u64),
+                1_000,
+            );
+        });
+    send_offer(&mut world, OTHER_BUYER, 1, 200, 0);
+    assert_eq!(reserved_buffer(&mut world), (1_000, 1_000));
+
+    // Nor can it be redeemed by withdrawing staked funds
+    world
+        .tx()
+        .from(OTHER_BUYER)
+        .to(XOXNO)
+        .returns(ExpectMessage(
+            "Not enough liquid EGLD to redeem staked funds, try again later!",
+        ))
+        .whitebox(xoxno_protocol::contract_obj, |sc| {
+            sc.withdraw_deposit(
+                &EgldOrEsdtTokenIdentifier::egld(),
+                0,
+                &BigUint::from(201u64),
+            )
+        });
+}
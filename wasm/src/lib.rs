@@ -6,10 +6,10 @@
 
 // Init:                                 1
 // Upgrade:                              1
-// Endpoints:                           95
+// Endpoints:                          242
 // Async Callback (empty):               1
 // Promise callbacks:                    1
-// Total number of exported functions:  99
+// Total number of exported functions: 246
 
 #![no_std]
 
@@ -22,14 +22,21 @@ multiversx_sc_wasm_adapter::endpoints! {
         init => init
         upgrade => upgrade
         listing => listing
+        listBundle => list_bundle
         bid => bid
         endAuction => end_auction
         buy => buy
         buySwap => buy_swap
         buyFor => buy_for
         bulkBuy => bulk_buy
+        bulkBuyReferred => bulk_buy_referred
         withdraw => withdraw
+        withdrawAllListings => withdraw_all_listings
         changeListing => bulk_change_listing
+        getRoleMembers => role_members
+        getLiquidStakingSC => liquid_staking_sc
+        getLiquidStakingToken => liquid_staking_token
+        getXoxnoToken => xoxno_token
         getMarketplaceCutPercentage => bid_cut_percentage
         getOffersByWallet => offers_by_wallet
         checkOfferSent => check_offer_sent
@@ -41,6 +48,7 @@ multiversx_sc_wasm_adapter::endpoints! {
         getTokenItemsQuantityForSale => token_items_quantity_for_sale
         getAcceptedTokens => accepted_tokens
         blackListWallets => blacklist_wallets
+        getBlacklistEntry => blacklist_entry
         whitelistedContracts => whitelisted_contracts
         getClaimableAmount => claimable_amount
         getClaimableTokens => claimable_tokens
@@ -48,6 +56,11 @@ multiversx_sc_wasm_adapter::endpoints! {
         getCollectionsListed => collections_listed
         getAllListings => listings
         getOffers => offers
+        isFeaturePaused => feature_paused
+        getHaltedCollections => halted_collections
+        getDeniedCollections => denied_collections
+        getAllowedCollections => allowed_collections
+        isCuratedMode => curated_mode
         getStatus => status
         getFullAuctionData => auction_by_id
         offerById => offer_by_id
@@ -60,8 +73,12 @@ multiversx_sc_wasm_adapter::endpoints! {
         userGlobalOffers => user_global_offers
         userCollectionGlobalOffers => user_collection_global_offers
         getCollectionConfig => collection_config
+        getCreatorSource => creator_source
         freezedAuctions => freezed_auctions
         getAccumulator => accumulator
+        isBlacklisted => is_blacklisted
+        getPauseState => get_pause_state
+        isCollectionHalted => is_collection_halted
         getListingsCount => get_listings_count
         getOffersCount => get_offers_count
         getGlobalOffersCount => get_global_offers_count
@@ -86,13 +103,22 @@ multiversx_sc_wasm_adapter::endpoints! {
         acceptOffer => accept_offer
         declineOffer => decline_offer
         withdrawOffer => withdraw_offer
+        withdrawAllOffers => withdraw_all_offers
         sendOffer => send_offer
         sendGlobalOffer => send_global_offer
         withdrawGlobalOffer => withdraw_global_offer
         acceptGlobalOffer => accept_global_offer
+        pruneUnfundedOffers => prune_unfunded_offers
+        pruneUnfundedGlobalOffers => prune_unfunded_global_offers
+        getUnfundedOffers => get_unfunded_offers
+        getUnfundedGlobalOffers => get_unfunded_global_offers
         returnListing => return_listing
+        purgeBlacklistedWallet => purge_blacklisted_wallet
         withdrawGlobalOffers => withdraw_global_offers
         withdrawCustomOffers => delete_custom_offers
+        grantRole => grant_role
+        revokeRole => revoke_role
+        hasRole => has_role
         addRewardBalance => add_reward_balance
         removeRewardBalance => remove_reward_balance
         setRewardTicker => set_reward_ticker
@@ -102,20 +128,141 @@ multiversx_sc_wasm_adapter::endpoints! {
         addWhitelist => add_whitelisted_sc
         removeWhitelist => remove_wl_sc
         setStatus => set_status
+        setFeaturePaused => set_feature_paused
+        haltCollection => halt_collection
+        resumeCollection => resume_collection
+        setStrictBalanceMode => set_strict_balance_mode
+        setLiquidStaking => set_liquid_staking
+        setFeeTiers => set_fee_tiers
+        setXoxnoToken => set_xoxno_token
+        setXoxnoHolderDiscount => set_xoxno_holder_discount
+        setXoxnoLockPeriod => set_xoxno_lock_period
+        setXoxnoRateMaxAge => set_xoxno_rate_max_age
+        setXoxnoFeePayment => set_xoxno_fee_payment
         setCutPercentage => set_percentage_cut
+        setCreatorSource => set_creator_source
+        removeCreatorSource => remove_creator_source
+        setReferrer => set_referrer
+        removeReferrer => remove_referrer
+        setTokenCutPercentage => set_token_cut_percentage
+        removeTokenCutPercentage => remove_token_cut_percentage
         unFreezeAuctionId => un_freeze_auction_id
         unFreezeAllAuctionIds => un_freeze_all_auction_id
         freezeAuctionId => freeze_auction_id
+        addDeniedCollections => add_denied_collections
+        removeDeniedCollections => remove_denied_collections
+        addAllowedCollections => add_allowed_collections
+        removeAllowedCollections => remove_allowed_collections
+        setCuratedMode => set_curated_mode
         addBlackListWallet => add_blacklist
         removeBlackListWallet => remove_blacklist
         setCutFeesReverted => set_cut_fees_reverted
         setRoyaltiesReverted => set_royalties_reverted
         setExtraFees => set_extra_fees
         setCustomRoyalties => set_custom_royalties
+        setRoyaltySplits => set_royalty_splits
         setConfigAdmin => set_config_admin
         deposit => deposit
+        depositFor => deposit_for
         withdrawDeposit => withdraw_deposit
+        setYieldMode => set_yield_mode
+        getFreeBalance => get_free_balance
+        getCommittedBalance => committed_funds
+        isStrictBalanceMode => strict_balance_mode
         userDeposit => user_funds
+        fundYieldBuffer => fund_yield_buffer
+        unbondYieldShares => unbond_yield_shares
+        claimYieldUnbond => claim_yield_unbond
+        refreshYieldRate => refresh_yield_rate
+        isYieldMode => yield_mode
+        getYieldShares => yield_shares
+        getTotalYieldShares => total_yield_shares
+        getYieldRate => yield_rate
+        getYieldBuffer => yield_buffer
+        getYieldBufferReserved => yield_buffer_reserved
+        getYieldReservation => yield_reservation
+        getPendingUnbondShares => pending_unbond_shares
+        getYieldUnbonds => yield_unbond_nonces
+        claim => claim
+        claimToken => claim_token
+        setClaimReceiver => set_claim_receiver
+        getClaimables => get_claimables
+        getClaimReceiver => claim_receiver
+        lockXoxno => lock_xoxno
+        unlockXoxno => unlock_xoxno
+        setPayFeesInXoxno => set_pay_fees_in_xoxno
+        getEffectiveFee => get_effective_fee
+        getEffectiveFees => get_effective_fees
+        getUserFeeTier => get_user_fee_tier
+        getTokenCutPercentage => token_cut_percentage
+        getFeeOverrideTokens => fee_override_tokens
+        getXoxnoLocked => xoxno_locked
+        isPayingFeesInXoxno => pay_fees_in_xoxno
+        getXoxnoHolderThreshold => xoxno_holder_threshold
+        getXoxnoHolderDiscount => xoxno_holder_discount
+        getXoxnoFeeRate => xoxno_fee_rate
+        getXoxnoFeeRateUpdated => xoxno_fee_rate_updated
+        getXoxnoRateMaxAge => xoxno_rate_max_age
+        getXoxnoLockPeriod => xoxno_lock_period
+        getXoxnoUnlockTime => xoxno_unlock_time
+        getXoxnoFeeDiscount => xoxno_fee_discount
+        getFeeTiers => fee_tiers
+        getVolumeWindowEpochs => volume_window_epochs
+        getReferrers => referrers
+        getReferrerShare => referrer_share
+        getReferralEarnings => referral_earnings
+        getReferralTrades => referral_trades
+        setGovernanceDelay => set_governance_delay
+        setAggregator => set_aggregator
+        setAccumulator => set_accumulator
+        setWrapping => set_wrapping
+        setClaimReceiverFor => set_claim_receiver_for
+        proposeAction => propose_action
+        executeAction => execute_action
+        cancelAction => cancel_action
+        getQueuedActions => get_queued_actions
+        getGovernanceDelay => governance_delay
+        getLastActionId => last_action_id
+        getQueuedAction => queued_action
+        escrowNfts => escrow_nfts
+        withdrawEscrow => withdraw_escrow
+        cancelSignedOrders => cancel_signed_orders
+        invalidateSignedOrders => invalidate_signed_orders
+        buySignedOrder => buy_signed_order
+        acceptSignedOffer => accept_signed_offer
+        cancelSignedOffer => cancel_signed_offer
+        cancelAllSignedOffers => cancel_all_signed_offers
+        getSignedOfferCounter => signed_offer_counter
+        isSignedOfferUsed => signed_offer_used
+        getEscrowBalance => escrow_balance
+        getMinOrderNonce => min_order_nonce
+        isOrderNonceUsed => order_nonce_used
+        createSwap => create_swap
+        fillSwap => fill_swap
+        setSwapRoyalty => set_swap_royalty
+        cancelSwap => cancel_swap
+        getLastSwapId => last_swap_id
+        getSwap => swap_by_id
+        getSwapRoyalty => swap_royalty
+        getSwapsByWallet => swaps_by_wallet
+        listRental => list_rental
+        rent => rent
+        returnRental => return_rental
+        claimRentalCollateral => claim_rental_collateral
+        cancelRental => cancel_rental
+        getLastRentalId => last_rental_id
+        getRental => rental_by_id
+        getRentalsByWallet => rentals_by_wallet
+        getRentalsByRenter => rentals_by_renter
+        listRaffle => list_raffle
+        buyTickets => buy_tickets
+        drawRaffle => draw_raffle
+        refundRaffle => refund_raffle
+        getLastRaffleId => last_raffle_id
+        getRaffle => raffle_by_id
+        getRafflesByWallet => raffles_by_wallet
+        getRaffleTickets => raffle_tickets
+        getRaffleParticipantTickets => raffle_participant_tickets
         callback_ash => callback_ash
     )
 }